@@ -0,0 +1,212 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::errors::{
+    ERR_NOT_COMMITTED_MAZE,
+    ERR_OUT_OF_BOUNDS,
+    ERR_CELL_REVEALED,
+    ERR_INVALID_BLOCK,
+    ERR_INVALID_PROOF,
+    ERR_PRIZE_NOT_HELD,
+    ERR_TELEPORT_NOT_PAIRED,
+};
+
+use crate::storage::{
+    Block,
+    Move,
+    Position,
+};
+
+// Maze layout committed through the Merkle root of its blocks, each block being revealed with its salt and Merkle proof.
+// A leaf is the keccak256 of the encoded row, column, block and 32 bytes salt, and each parent the keccak256 of its two children sorted.
+// Each cell needs its own secret random salt, otherwise the leaves of a revealed proof could be brute-forced to read the unrevealed cells.
+#[multiversx_sc::module]
+pub trait CommitmentModule:
+    crate::storage::StorageModule
+    + crate::validation::ValidationModule
+    + crate::movement::MovementModule
+    + crate::events::EventsModule
+{
+    // Function used by anyone to reveal a block of a committed maze with its salt and Merkle proof.
+    // Teleports are revealed in pairs through revealTeleportPair.
+    #[endpoint(revealCell)]
+    #[allow(clippy::too_many_arguments)]
+    fn reveal_cell(
+        &self,
+        game_id: u64,
+        row: usize,
+        col: usize,
+        block: Block<Self::Api>,
+        salt: ManagedByteArray<Self::Api, 32>,
+        proof: MultiValueEncoded<ManagedByteArray<Self::Api, 32>>,
+    ) {
+        require!(
+            !matches!(block, Block::Teleport(_)),
+            ERR_TELEPORT_NOT_PAIRED
+        );
+        self.reveal_block(game_id, row, col, block, &salt, proof.to_vec());
+    }
+
+    // Function used by anyone to reveal the two teleports sharing "pair_id" with their salts and Merkle proofs.
+    // A teleport is never revealed without its partner, so the player always lands on the partner.
+    #[endpoint(revealTeleportPair)]
+    #[allow(clippy::too_many_arguments)]
+    fn reveal_teleport_pair(
+        &self,
+        game_id: u64,
+        pair_id: u64,
+        first: Position,
+        first_salt: ManagedByteArray<Self::Api, 32>,
+        first_proof: ManagedVec<ManagedByteArray<Self::Api, 32>>,
+        second: Position,
+        second_salt: ManagedByteArray<Self::Api, 32>,
+        second_proof: ManagedVec<ManagedByteArray<Self::Api, 32>>,
+    ) {
+        self.reveal_block(game_id, first.row, first.col, Block::Teleport(pair_id), &first_salt, first_proof);
+        self.reveal_block(game_id, second.row, second.col, Block::Teleport(pair_id), &second_salt, second_proof);
+    }
+
+    // Function used to check the Merkle proof of a block of a committed maze and store the block.
+    fn reveal_block(
+        &self,
+        game_id: u64,
+        row: usize,
+        col: usize,
+        block: Block<Self::Api>,
+        salt: &ManagedByteArray<Self::Api, 32>,
+        proof: ManagedVec<ManagedByteArray<Self::Api, 32>>,
+    ) {
+        require!(
+            !self.matrix_root(game_id).is_empty(),
+            ERR_NOT_COMMITTED_MAZE
+        );
+        let position = Position { row, col };
+        require!(
+            self.is_in_maze(&self.matrix_dimensions(game_id).get(), &position),
+            ERR_OUT_OF_BOUNDS
+        );
+        require!(
+            self.matrix(game_id, row).get(col) == Block::Unknown,
+            ERR_CELL_REVEALED
+        );
+        require!(
+            !matches!(block, Block::Unknown | Block::MatrixDimensions(_, _)),
+            ERR_INVALID_BLOCK
+        );
+        require!(
+            self.get_merkle_root(self.get_cell_leaf(row, col, &block, salt), proof) == self.matrix_root(game_id).get(),
+            ERR_INVALID_PROOF
+        );
+        if let Block::Prize(prize_info) = &block {
+            require!(
+                self.blockchain().get_sc_balance(&prize_info.ticker, prize_info.nonce) >= prize_info.quantity,
+                ERR_PRIZE_NOT_HELD
+            );
+        }
+
+        self.matrix(game_id, row).set(col, &block);
+        self.cell_revealed_event(game_id, &self.blockchain().get_caller(), &position, &block);
+        self.register_block(game_id, row, col, block);
+    }
+
+    // Merkle leaf of the block at "row" and "col", salted with the secret salt of the cell.
+    #[view(getCellLeaf)]
+    fn get_cell_leaf(
+        &self,
+        row: usize,
+        col: usize,
+        block: &Block<Self::Api>,
+        salt: &ManagedByteArray<Self::Api, 32>,
+    ) -> ManagedByteArray<Self::Api, 32> {
+        let mut data = ManagedBuffer::new();
+        let _ = row.dep_encode(&mut data);
+        let _ = col.dep_encode(&mut data);
+        let _ = block.dep_encode(&mut data);
+        data.append(salt.as_managed_buffer());
+        self.crypto().keccak256(data)
+    }
+
+    // Blocks to reveal before the round can be resolved: the unknown blocks on the path of the winning move, picked once the round is over.
+    // The path stops at the maze edge and at the first revealed obstacle.
+    // The start block is also needed once a trap or a random block, that can send the player back to the start, is on the path.
+    #[view(getCellsToReveal)]
+    fn get_cells_to_reveal(&self, game_id: u64) -> MultiValueEncoded<Position> {
+        let mut cells: ManagedVec<Position> = ManagedVec::new();
+        if self.matrix_root(game_id).is_empty() || self.pending_winning_move(game_id).is_empty() {
+            return cells.into();
+        }
+
+        let winning_move = self.pending_winning_move(game_id).get();
+        let jump = self.get_jump_size(&winning_move.payment_token, &winning_move.payment_amount);
+        let dimensions = self.matrix_dimensions(game_id).get();
+        let position = self.player_position(game_id).get();
+        let keys = self.collected_keys_id(game_id);
+        let mut back_to_start = false;
+        for step in 1..=jump {
+            if !self.fits_in_maze(&dimensions, &position, &winning_move.player_move, step) {
+                break;
+            }
+            let cell = self.get_new_position(&position, &winning_move.player_move, step);
+            match self.matrix(game_id, cell.row).get(cell.col) {
+                Block::Unknown => cells.push(cell),
+                Block::Wall => break,
+                Block::Trap => {
+                    back_to_start = true;
+                    break;
+                },
+                Block::Random(_) => back_to_start = true,
+                Block::Door(door_id) => {
+                    if !keys.contains(&door_id) {
+                        break;
+                    }
+                },
+                _ => (),
+            }
+        }
+
+        let start = self.start_position(game_id).get();
+        if back_to_start && self.matrix(game_id, start.row).get(start.col) == Block::Unknown && !cells.contains(&start) {
+            cells.push(start);
+        }
+        cells.into()
+    }
+
+    // Function used to know if all the blocks on the path of "player_move" and "jump" from "from" are revealed.
+    // Outcomes moving the player, like the random moves, are restricted to revealed paths.
+    fn is_path_revealed(
+        &self,
+        game_id: u64,
+        from: &Position,
+        jump: usize,
+        player_move: &Move,
+    ) -> bool {
+        for step in 1..=jump {
+            let cell = self.get_new_position(from, player_move, step);
+            if self.matrix(game_id, cell.row).get(cell.col) == Block::Unknown {
+                return false;
+            }
+        }
+        true
+    }
+
+    // Function used to get the Merkle root from a leaf and its proof, pairs being hashed sorted.
+    fn get_merkle_root(
+        &self,
+        leaf: ManagedByteArray<Self::Api, 32>,
+        proof: ManagedVec<ManagedByteArray<Self::Api, 32>>,
+    ) -> ManagedByteArray<Self::Api, 32> {
+        let mut node = leaf;
+        for sibling in proof.iter() {
+            let mut data = ManagedBuffer::new();
+            if node.to_byte_array() <= sibling.to_byte_array() {
+                data.append(node.as_managed_buffer());
+                data.append(sibling.as_managed_buffer());
+            } else {
+                data.append(sibling.as_managed_buffer());
+                data.append(node.as_managed_buffer());
+            }
+            node = self.crypto().keccak256(data);
+        }
+        node
+    }
+}
@@ -1,16 +1,74 @@
 pub const ERR_CONTRACT_PAUSED: &str = "Contract is paused!";
 pub const ERR_GAME_ENDED: &str = "Game has ended!";
+pub const ERR_GAME_NOT_STARTED: &str = "Game hasn't started!";
+pub const ERR_GAME_NOT_FOUND: &str = "Game doesn't exist or has been closed!";
+pub const ERR_ROUND_CLOSED: &str = "Round is closed, waiting for the winning move to be picked!";
+pub const ERR_ROUND_RESOLVING: &str = "Round is resolving, waiting for the path of the winning move to be revealed!";
+pub const ERR_NO_ROUND: &str = "No round to resolve!";
+pub const ERR_ROUND_ONGOING: &str = "Round duration isn't over yet!";
 pub const ERR_GAME_ONGOING: &str = "Game hasn't ended, action unauthorized!";
 pub const ERR_PAYMENT_IDENTIFIER: &str = "Invalid payment token!";
 pub const ERR_PAYMENT_NONCE: &str = "Invalid payment nonce!";
+pub const ERR_PAYMENT_ZERO: &str = "Payment amount must be positive!";
 pub const ERR_PAYMENT_AMOUNT: &str = "Amount paid is not a multiple of the set move price!";
+pub const ERR_NOT_ENOUGH_CREDIT: &str = "Not enough credit!";
+pub const ERR_CREDIT_TOKEN: &str = "Credit must be spent in the payment token!";
+pub const ERR_PRICE_ZERO: &str = "Price of a jump must be positive!";
+pub const ERR_NO_CREDIT: &str = "No credit to claim!";
+pub const ERR_REFUND_PERCENT: &str = "Refund percent can't exceed 100%!";
+pub const ERR_COMMIT_REVEAL_MODE: &str = "Moves must be committed with commitMove!";
+pub const ERR_NOT_COMMIT_REVEAL_MODE: &str = "Commit-reveal mode isn't enabled!";
+pub const ERR_NOT_REVEAL_WINDOW: &str = "Moves can only be revealed during the reveal window!";
+pub const ERR_NO_COMMIT: &str = "No committed move to reveal!";
+pub const ERR_COMMIT_MISMATCH: &str = "Revealed move doesn't match the committed hash!";
+pub const ERR_ROUND_NOT_IDLE: &str = "All rounds must be idle!";
+pub const ERR_ROUND_NOT_FOUND: &str = "Round hasn't been resolved!";
+pub const ERR_GAME_NOT_ARCHIVED: &str = "Game session not completed!";
 
 pub const ERR_KEY_NEEDED: &str = "You need a key to go through this door!";
 pub const ERR_JUMP_OVER_TRAP: &str = "You can't jump over a trap";
 pub const ERR_GO_THROUGH_WALL: &str = "Can't go through a wall.";
+pub const ERR_OUT_OF_BOUNDS: &str = "Move leaves the maze!";
 
-pub const ERR_MVP_CLAIMED: &str = "MVP reward already claimed!";
-pub const ERR_NOT_MVP: &str = "You are not the mvp!";
+pub const ERR_RAGGED_ROWS: &str = "All matrix rows must have the same length!";
+pub const ERR_INVALID_BLOCK: &str = "Matrix dimensions and unknown blocks can't be stored in the matrix!";
+pub const ERR_START_MISSING: &str = "Matrix needs a start block!";
+pub const ERR_DUPLICATE_START: &str = "Matrix can't have more than one start block!";
+pub const ERR_FINISH_MISSING: &str = "Matrix needs a finish prize!";
+pub const ERR_DUPLICATE_FINISH: &str = "Matrix can't have more than one finish prize!";
+pub const ERR_DOOR_WITHOUT_KEY: &str = "Every door needs a matching key in the matrix!";
+pub const ERR_TELEPORT_PAIR: &str = "Each teleport needs exactly one partner!";
+pub const ERR_NO_RANDOM_TABLE: &str = "Every random block needs an outcome table!";
+pub const ERR_TOO_MANY_KEYS: &str = "Maze solver supports at most 64 keys!";
+pub const ERR_MATRIX_NOT_SET: &str = "Matrix hasn't been set!";
+pub const ERR_MATRIX_ALREADY_SET: &str = "Matrix already set, it must be cleared first!";
+pub const ERR_MAZE_UPLOAD_ONGOING: &str = "Maze upload ongoing!";
+pub const ERR_NO_MAZE_UPLOAD: &str = "No maze upload ongoing!";
+pub const ERR_CHUNK_ORDER: &str = "Blocks of a row must be uploaded in order!";
+pub const ERR_MAZE_UPLOAD_INCOMPLETE: &str = "All the blocks of the maze must be uploaded!";
+pub const ERR_MATRIX_NOT_EDITABLE: &str = "Matrix can only be edited while the game isn't started or the contract is paused!";
+pub const ERR_COMMITTED_MAZE: &str = "Committed maze can't be edited!";
+pub const ERR_NOT_COMMITTED_MAZE: &str = "Maze layout isn't committed!";
+pub const ERR_CELL_REVEALED: &str = "Block already revealed!";
+pub const ERR_INVALID_PROOF: &str = "Invalid Merkle proof!";
+pub const ERR_TELEPORT_NOT_PAIRED: &str = "Teleports must be revealed in pairs!";
+pub const ERR_CELLS_NOT_REVEALED: &str = "Blocks on the path of the winning move must be revealed!";
+pub const ERR_CELLS_REVEALED: &str = "Blocks on the paths of the moves are revealed, the round must be resolved!";
+pub const ERR_REVEAL_TIMEOUT_ONGOING: &str = "Cell reveal timeout isn't over yet!";
+pub const ERR_NO_REVEAL_TIMEOUT: &str = "Cell reveal timeout must be set!";
+pub const ERR_PRIZE_NOT_HELD: &str = "Contract doesn't hold the tokens of the prizes to win!";
+
+pub const ERR_SPLIT_TOTAL: &str = "Revenue split shares must sum to 100%!";
+pub const ERR_SPLIT_NOT_SET: &str = "Revenue split must be set before creating a game!";
+pub const ERR_SHARE_DISTRIBUTED: &str = "Share already distributed!";
+pub const ERR_NO_TREASURY_ADDRESS: &str = "Treasury address isn't set!";
+pub const ERR_NO_ROYALTY_ADDRESS: &str = "Royalty address isn't set!";
+pub const ERR_CURVE_TOTAL: &str = "Rank curve shares must sum to 100%!";
+pub const ERR_NO_RANK_REWARD: &str = "No reward for this rank!";
+pub const ERR_RANK_CLAIMED: &str = "Rank reward already claimed!";
+pub const ERR_NOT_AT_RANK: &str = "You are not at this rank of the leaderboard!";
+pub const ERR_PATHFINDER_CLAIMED: &str = "Pathfinder reward already claimed!";
+pub const ERR_NO_PICKED_MOVE: &str = "None of your moves has been picked!";
 
 
 
@@ -0,0 +1,192 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::storage::{
+    Block,
+    Dimensions,
+    Move,
+    PaymentToken,
+    Position,
+    PrizeInfo,
+    RevenueShare,
+    RandomOutcome,
+};
+
+#[multiversx_sc::module]
+pub trait EventsModule {
+
+    // Outcome rolled when a player lands on a random block.
+    #[event("randomOutcome")]
+    fn random_outcome_event(
+        &self,
+        #[indexed] game_id: u64,
+        #[indexed] address: &ManagedAddress,
+        #[indexed] table_id: u64,
+        #[indexed] position: &Position,
+        outcome: &RandomOutcome,
+    );
+
+    // Move registered by a player for the current round.
+    #[event("moveRegistered")]
+    fn move_registered_event(
+        &self,
+        #[indexed] game_id: u64,
+        #[indexed] address: &ManagedAddress,
+        #[indexed] player_move: &Move,
+        #[indexed] jump: usize,
+        payment: &PaymentToken<Self::Api>,
+    );
+
+    // Hidden move committed by a player in commit-reveal mode.
+    #[event("moveCommitted")]
+    fn move_committed_event(
+        &self,
+        #[indexed] game_id: u64,
+        #[indexed] address: &ManagedAddress,
+        #[indexed] hash: &ManagedByteArray<Self::Api, 32>,
+        payment: &PaymentToken<Self::Api>,
+    );
+
+    // Move of a player replaced by a new move during the same round.
+    #[event("moveReplaced")]
+    fn move_replaced_event(
+        &self,
+        #[indexed] game_id: u64,
+        #[indexed] address: &ManagedAddress,
+        #[indexed] player_move: &Move,
+        #[indexed] jump: usize,
+        payment: &PaymentToken<Self::Api>,
+    );
+
+    // Winning move of a round, with the position of the player once the move is applied.
+    #[event("roundResolved")]
+    fn round_resolved_event(
+        &self,
+        #[indexed] game_id: u64,
+        #[indexed] address: &ManagedAddress,
+        #[indexed] player_move: &Move,
+        #[indexed] jump: usize,
+        position: &Position,
+    );
+
+    // Round of a committed maze cancelled, its blocks not being revealed in time, all its moves being refunded.
+    #[event("roundCancelled")]
+    fn round_cancelled_event(
+        &self,
+        #[indexed] game_id: u64,
+        #[indexed] address: &ManagedAddress,
+    );
+
+    // Key picked up by a player.
+    #[event("keyCollected")]
+    fn key_collected_event(
+        &self,
+        #[indexed] game_id: u64,
+        #[indexed] address: &ManagedAddress,
+        #[indexed] key_id: u64,
+        position: &Position,
+    );
+
+    // Door opened by a player with a collected key.
+    #[event("doorOpened")]
+    fn door_opened_event(
+        &self,
+        #[indexed] game_id: u64,
+        #[indexed] address: &ManagedAddress,
+        #[indexed] door_id: u64,
+        position: &Position,
+    );
+
+    // Trap triggered by a player, sending the player back to the start position.
+    #[event("trapTriggered")]
+    fn trap_triggered_event(
+        &self,
+        #[indexed] game_id: u64,
+        #[indexed] address: &ManagedAddress,
+        position: &Position,
+    );
+
+    // Prize won by a player.
+    #[event("prizeWon")]
+    fn prize_won_event(
+        &self,
+        #[indexed] game_id: u64,
+        #[indexed] address: &ManagedAddress,
+        #[indexed] position: &Position,
+        prize: &PrizeInfo<Self::Api>,
+    );
+
+    // Game session ended, by the player reaching the finish or by the owner closing the game.
+    #[event("gameEnded")]
+    fn game_ended_event(
+        &self,
+        #[indexed] game_id: u64,
+        #[indexed] address: &ManagedAddress,
+        collected_value: &BigUint,
+    );
+
+    // Rewards share of a leaderboard rank claimed for a payment token.
+    #[event("rankRewardClaimed")]
+    fn rank_reward_claimed_event(
+        &self,
+        #[indexed] game_id: u64,
+        #[indexed] address: &ManagedAddress,
+        #[indexed] rank: usize,
+        share: &PaymentToken<Self::Api>,
+    );
+
+    // Share of the revenue split of a payment token paid, the jackpot share being kept for the next game session.
+    #[event("shareDistributed")]
+    fn share_distributed_event(
+        &self,
+        #[indexed] game_id: u64,
+        #[indexed] address: &ManagedAddress,
+        #[indexed] share: &RevenueShare,
+        payment: &PaymentToken<Self::Api>,
+    );
+
+    // Jackpot of a payment token won by the player reaching the finish.
+    #[event("jackpotWon")]
+    fn jackpot_won_event(
+        &self,
+        #[indexed] game_id: u64,
+        #[indexed] address: &ManagedAddress,
+        payment: &PaymentToken<Self::Api>,
+    );
+
+    // Pathfinder pool share of a payment token claimed by a player whose moves got picked.
+    #[event("pathfinderRewardClaimed")]
+    fn pathfinder_reward_claimed_event(
+        &self,
+        #[indexed] game_id: u64,
+        #[indexed] address: &ManagedAddress,
+        payment: &PaymentToken<Self::Api>,
+    );
+
+    // Matrix of a game session set by the owner.
+    #[event("matrixSet")]
+    fn matrix_set_event(
+        &self,
+        #[indexed] game_id: u64,
+        #[indexed] address: &ManagedAddress,
+        dimensions: &Dimensions,
+    );
+
+    // Block of a committed maze revealed with its Merkle proof.
+    #[event("cellRevealed")]
+    fn cell_revealed_event(
+        &self,
+        #[indexed] game_id: u64,
+        #[indexed] address: &ManagedAddress,
+        #[indexed] position: &Position,
+        block: &Block<Self::Api>,
+    );
+
+    // Matrix of a game session cleared by the owner.
+    #[event("matrixCleared")]
+    fn matrix_cleared_event(
+        &self,
+        #[indexed] game_id: u64,
+        #[indexed] address: &ManagedAddress,
+    );
+}
@@ -0,0 +1,111 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::errors::{
+    ERR_ROUND_NOT_FOUND,
+    ERR_GAME_NOT_ARCHIVED,
+};
+
+use crate::storage::{
+    GameSummary,
+    PaymentToken,
+    PrizeToWin,
+    RoundRecord,
+};
+
+// Archive of the completed game sessions, kept when the matrix is cleared, and of the rounds of each game session.
+#[multiversx_sc::module]
+pub trait HistoryModule:
+    crate::storage::StorageModule
+{
+    // Function used to get "count" summaries of the completed game sessions, from the "from"-th completed game (starting at 1 like the rounds, 0 being read as 1).
+    #[view(getGameHistory)]
+    fn get_game_history(&self, from: usize, count: usize) -> MultiValueEncoded<GameSummary<Self::Api>> {
+        let mut history: MultiValueEncoded<GameSummary<Self::Api>> = MultiValueEncoded::new();
+        let len = self.game_history().len();
+        let first_index = core::cmp::max(from, 1);
+        let mut index = first_index;
+        while index <= len && index < first_index.saturating_add(count) {
+            history.push(self.game_history().get(index));
+            index += 1;
+        }
+        history
+    }
+
+    // Function used to get the summary of a completed game session, the latest one if the game session has been replayed.
+    #[view(getGameSummary)]
+    fn get_game_summary(&self, game_id: u64) -> GameSummary<Self::Api> {
+        require!(
+            !self.game_summary_index(game_id).is_empty(),
+            ERR_GAME_NOT_ARCHIVED
+        );
+        self.game_history().get(self.game_summary_index(game_id).get())
+    }
+
+    // Function used to get "count" records of the rounds of a game session, from round "from" (starting at 1, round 0 being read as round 1).
+    #[view(getRoundRecords)]
+    fn get_round_records(&self, game_id: u64, from: usize, count: usize) -> MultiValueEncoded<RoundRecord<Self::Api>> {
+        let mut records: MultiValueEncoded<RoundRecord<Self::Api>> = MultiValueEncoded::new();
+        let len = self.round_records(game_id).len();
+        let first_round = core::cmp::max(from, 1);
+        let mut round = first_round;
+        while round <= len && round < first_round.saturating_add(count) {
+            records.push(self.round_records(game_id).get(round));
+            round += 1;
+        }
+        records
+    }
+
+    // Function used to get the record of a round of a game session, rounds starting at 1.
+    #[view(getRoundRecord)]
+    fn get_round_record(&self, game_id: u64, round: usize) -> RoundRecord<Self::Api> {
+        require!(
+            round >= 1 && round <= self.round_records(game_id).len(),
+            ERR_ROUND_NOT_FOUND
+        );
+        self.round_records(game_id).get(round)
+    }
+
+    // Function used to record the summary of a completed game session.
+    fn archive_game(&self, game_id: u64) {
+        let mut prize_winners: ManagedVec<PrizeToWin<Self::Api>> = ManagedVec::new();
+        for prize in self.prizes_to_win(game_id).iter() {
+            if !prize.winner.is_zero() {
+                prize_winners.push(prize);
+            }
+        }
+        let mut collected: ManagedVec<PaymentToken<Self::Api>> = ManagedVec::new();
+        for token in self.game_tokens(game_id).iter() {
+            collected.push(PaymentToken {
+                amount: self.collected_tokens(game_id, &token).get(),
+                ticker: token,
+                nonce: 0,
+            });
+        }
+
+        self.game_history().push(&GameSummary {
+            game_id,
+            start_timestamp: self.game_start(game_id).get(),
+            end_timestamp: self.blockchain().get_block_timestamp(),
+            matrix_hash: self.matrix_hash(game_id).get(),
+            rounds: self.rounds_played(game_id).get(),
+            mvp: self.get_mvp(game_id),
+            prize_winners,
+            collected,
+            collected_value: self.get_collected_value(game_id),
+        });
+        self.game_summary_index(game_id).set(self.game_history().len());
+    }
+
+    // Function used to get the keccak256 hash of the matrix blocks, row by row.
+    fn get_matrix_hash(&self, game_id: u64) -> ManagedByteArray<Self::Api, 32> {
+        let dimensions = self.matrix_dimensions(game_id).get();
+        let mut data = ManagedBuffer::new();
+        for row in 1..=dimensions.rows {
+            for block in self.matrix(game_id, row).iter() {
+                let _ = block.dep_encode(&mut data);
+            }
+        }
+        self.crypto().keccak256(data)
+    }
+}
@@ -8,39 +8,78 @@ pub const MULTIPLIER_TOTAL: u64 = 1000000000000000000;
 pub mod storage;
 pub mod errors;
 pub mod setup;
+pub mod events;
+pub mod validation;
+pub mod movement;
+pub mod solver;
+pub mod revenue;
+pub mod history;
+pub mod commitment;
 
 use errors::{
     ERR_CONTRACT_PAUSED,
     ERR_GAME_ENDED,
+    ERR_GAME_NOT_STARTED,
+    ERR_GAME_NOT_FOUND,
+    ERR_ROUND_CLOSED,
+    ERR_ROUND_RESOLVING,
+    ERR_NO_ROUND,
+    ERR_ROUND_ONGOING,
+    ERR_CELLS_NOT_REVEALED,
+    ERR_CELLS_REVEALED,
+    ERR_REVEAL_TIMEOUT_ONGOING,
     ERR_PAYMENT_IDENTIFIER,
     ERR_PAYMENT_NONCE,
     ERR_PAYMENT_AMOUNT,
+    ERR_NOT_ENOUGH_CREDIT,
+    ERR_CREDIT_TOKEN,
+    ERR_NO_CREDIT,
+    ERR_COMMIT_REVEAL_MODE,
+    ERR_NOT_COMMIT_REVEAL_MODE,
+    ERR_NOT_REVEAL_WINDOW,
+    ERR_NO_COMMIT,
+    ERR_COMMIT_MISMATCH,
 
     ERR_GO_THROUGH_WALL,
     ERR_KEY_NEEDED,
-    ERR_JUMP_OVER_TRAP,
 
     ERR_GAME_ONGOING,
-    ERR_MVP_CLAIMED,
-    ERR_NOT_MVP,
+    ERR_NO_RANK_REWARD,
+    ERR_RANK_CLAIMED,
+    ERR_NOT_AT_RANK,
 };
 
 use storage::{
-    Position,
     PrizeType,
     PrizeInfo,
     PrizeToWin,
     Block,
     Move,
+    RandomOutcome,
     GameStatus,
+    RoundStatus,
     Status,
     PlayerMove,
+    MoveTotal,
+    SelectionMode,
+    LosingMovePolicy,
+    MoveCommit,
+    PaymentToken,
+    UnrevealedPolicy,
+    RoundRecord,
 };
 
 #[multiversx_sc::contract]
 pub trait Main: 
     storage::StorageModule
     + setup::SetupModule
+    + validation::ValidationModule
+    + movement::MovementModule
+    + solver::SolverModule
+    + revenue::RevenueModule
+    + history::HistoryModule
+    + commitment::CommitmentModule
+    + events::EventsModule
     + multiversx_sc_modules::pause::PauseModule
 {
     #[proxy]
@@ -51,45 +90,354 @@ pub trait Main:
     }
 
     #[endpoint(ping)]
-    fn ping(&self) -> ManagedBuffer {
+    fn ping(&self, game_id: u64) -> ManagedBuffer {
         let now = self.blockchain().get_block_timestamp();
+        let mut response = ManagedBuffer::new();
 
-        // New round of moves to register
-        self.timer().set(now);
-        self.can_make_move().set(true);
-
-        // The duration lower bound has been exceeded.
-        if now - self.timer().get() >= self.duration().get() {
-            self.timer().clear();
-            self.can_make_move().set(false);
+        // The clock only runs while a round is collecting moves or waiting for the blocks on the path of its winning move.
+        if self.round_status(game_id).get() == RoundStatus::Idle {
+            return response;
+        }
 
-            let winning_address = self.pick_move();
-            self.addresses_with_move().clear();
-            self.moves().clear();
+        // The duration lower bound (and the reveal window) has been exceeded since the first move of the round.
+        // In a committed maze, the blocks on the path of the winning move must also be revealed.
+        if now >= self.get_round_end(game_id) {
+            self.pick_pending_winning_move(game_id);
+            if self.get_cells_to_reveal(game_id).is_empty() {
+                let winning_address = self.resolve_round(game_id);
+                self.settle_game_end(game_id, &winning_address);
+                response.append(winning_address.as_managed_buffer());
+                return response;
+            }
+        }
 
-            let mut response = ManagedBuffer::new();
-            response.append(&winning_address.as_managed_buffer());
+        // The async clock is only an optional fallback to resolveRound.
+        if !self.async_clock().get() {
             return response;
         }
 
         self.ping_proxy(self.pong_address().get())
-            .pong()
+            .pong(game_id)
             .async_call()
             .call_and_exit();
     }
 
+    // Function used by anyone to resolve the round once its duration is over.
+    // The caller receives the keeper bounty, limited to the amount of payment tokens collected.
+    // In a committed maze, the first call only picks the winning move until the blocks on its path are revealed, and returns its address.
+    #[endpoint(resolveRound)]
+    fn resolve_round_endpoint(&self, game_id: u64) -> ManagedAddress {
+        let caller = self.blockchain().get_caller();
+        let now = self.blockchain().get_block_timestamp();
+
+        require!(
+            self.round_status(game_id).get() != RoundStatus::Idle,
+            ERR_NO_ROUND
+        );
+        require!(
+            now >= self.get_round_end(game_id),
+            ERR_ROUND_ONGOING
+        );
+        let collecting = self.round_status(game_id).get() == RoundStatus::Collecting;
+        self.pick_pending_winning_move(game_id);
+        if collecting && !self.get_cells_to_reveal(game_id).is_empty() {
+            return self.pending_winning_move(game_id).get().address;
+        }
+        require!(
+            self.get_cells_to_reveal(game_id).is_empty(),
+            ERR_CELLS_NOT_REVEALED
+        );
+
+        let winning_address = self.resolve_round(game_id);
+
+        for token in self.game_tokens(game_id).iter() {
+            let bounty = core::cmp::min(self.keeper_bounty(&token).get(), self.collected_tokens(game_id, &token).get());
+            if bounty > 0 {
+                self.collected_tokens(game_id, &token).update(|val| *val -= &bounty);
+                self.send()
+                        .direct(&caller, &token, 0, &bounty);
+            }
+        }
+        self.settle_game_end(game_id, &winning_address);
+        winning_address
+    }
+
+    // Function used once a round is fully settled, to freeze the payment tokens collected and archive the game session if the winning move reached the finish.
+    fn settle_game_end(&self, game_id: u64, winning_address: &ManagedAddress) {
+        if self.game_status(game_id).get() == GameStatus::End {
+            self.snapshot_collected_tokens(game_id);
+            self.archive_game(game_id);
+            self.game_ended_event(game_id, winning_address, &self.get_collected_value(game_id));
+        }
+    }
+
+    // Function used by anyone to cancel a round of a committed maze whose winning move path hasn't been revealed within the cell reveal timeout.
+    // The moves and commits of the round are refunded, whatever the losing moves policy.
+    #[endpoint(cancelRound)]
+    fn cancel_round(&self, game_id: u64) {
+        let now = self.blockchain().get_block_timestamp();
+
+        require!(
+            self.round_status(game_id).get() != RoundStatus::Idle,
+            ERR_NO_ROUND
+        );
+        require!(
+            now >= self.get_round_end(game_id) + self.cell_reveal_timeout().get(),
+            ERR_REVEAL_TIMEOUT_ONGOING
+        );
+        self.pick_pending_winning_move(game_id);
+        require!(
+            !self.get_cells_to_reveal(game_id).is_empty(),
+            ERR_CELLS_REVEALED
+        );
+
+        for address in self.addresses_with_move(game_id).iter() {
+            let p_move = self.player_move(game_id, &address).take();
+            self.refund_payment(game_id, &address, &p_move.payment_token, &p_move.payment_amount);
+        }
+        self.addresses_with_move(game_id).clear();
+        for address in self.committed_addresses(game_id).iter() {
+            let commit = self.commits(game_id, &address).take();
+            self.refund_payment(game_id, &address, &commit.payment_token, &commit.payment_amount);
+        }
+        self.committed_addresses(game_id).clear();
+        self.pending_winning_move(game_id).clear();
+
+        self.timer(game_id).clear();
+        self.round_status(game_id).set(RoundStatus::Idle);
+        self.round_cancelled_event(game_id, &self.blockchain().get_caller());
+    }
+
+    // Function used to pick and apply the winning move, then reset the round.
+    fn resolve_round(&self, game_id: u64) -> ManagedAddress {
+        self.round_status(game_id).set(RoundStatus::Resolving);
+        self.rounds_played(game_id).update(|val| *val += 1);
+        let position_before = self.player_position(game_id).get();
+        let mut total_paid = BigUint::zero();
+        for p_move in self.get_moves(game_id).iter() {
+            total_paid += self.get_weighted_value(&p_move.payment_token, &p_move.payment_amount);
+        }
+
+        // In commit-reveal mode, a round can end without any revealed move.
+        let mut winning_move = PlayerMove {
+            payment_token: EgldOrEsdtTokenIdentifier::egld(),
+            payment_nonce: 0,
+            payment_amount: BigUint::zero(),
+            player_move: Move::None,
+            address: ManagedAddress::zero(),
+        };
+        let mut block = self.matrix(game_id, position_before.row).get(position_before.col);
+        if !self.addresses_with_move(game_id).is_empty() {
+            (winning_move, block) = self.pick_move(game_id);
+        }
+        let winning_address = winning_move.address.clone();
+        self.round_records(game_id).push(&RoundRecord {
+            round: self.rounds_played(game_id).get(),
+            position_before,
+            position_after: self.player_position(game_id).get(),
+            winning_move,
+            moves_count: self.addresses_with_move(game_id).len(),
+            total_paid,
+            block,
+        });
+        for address in self.addresses_with_move(game_id).iter() {
+            let p_move = self.player_move(game_id, &address).take();
+            if address != winning_address {
+                self.settle_losing_move(game_id, &p_move);
+            }
+        }
+        self.addresses_with_move(game_id).clear();
+        self.settle_unrevealed_commits(game_id);
+
+        self.timer(game_id).clear();
+        self.round_status(game_id).set(RoundStatus::Idle);
+        winning_address
+    }
+
     #[payable("*")]
     #[endpoint(addMove)]
     #[allow(clippy::too_many_arguments)]
     fn add_move(
         &self,
+        game_id: u64,
         #[payment_token] payment_token  : EgldOrEsdtTokenIdentifier,
         #[payment_nonce] payment_nonce  : u64,
         #[payment_amount] payment_amount: BigUint,
         player_move: Move,
+        opt_credit: OptionalValue<MultiValue2<EgldOrEsdtTokenIdentifier, BigUint>>,
     ) {
         let caller = self.blockchain().get_caller();
 
+        // In commit-reveal mode, moves are registered through commitMove and revealMove.
+        require!(
+            !self.commit_reveal().get(),
+            ERR_COMMIT_REVEAL_MODE
+        );
+        self.require_round_open(game_id);
+
+        let payment = self.take_move_payment(&caller, payment_token, payment_nonce, payment_amount, opt_credit);
+
+        // The amount paid is a multiple of the token price, so jump size will always be a positive integer.
+        let jump_size = self.get_jump_size(&payment.ticker, &payment.amount);
+        
+        // Check move validity
+        self.check_move(game_id, jump_size, player_move.clone());
+
+        // If needed, remove the old move registered, it is settled like a losing move.
+        if !self.player_move(game_id, &caller).is_empty() {
+            let p_move = self.player_move(game_id, &caller).get();
+            self.settle_losing_move(game_id, &p_move);
+            self.move_replaced_event(
+                game_id,
+                &caller,
+                &p_move.player_move,
+                self.get_jump_size(&p_move.payment_token, &p_move.payment_amount),
+                &PaymentToken {
+                    ticker: p_move.payment_token.clone(),
+                    nonce: 0,
+                    amount: p_move.payment_amount.clone(),
+                },
+            );
+        }
+
+        // Set the new player move in this game's round registered moves.
+        self.move_registered_event(game_id, &caller, &player_move, jump_size, &payment);
+        self.player_move(game_id, &caller).set(
+            PlayerMove {
+                payment_token: payment.ticker.clone(),
+                payment_nonce: 0,
+                payment_amount: payment.amount.clone(),
+                player_move,
+                address: caller.clone(),
+            }
+        );
+
+        // Add address to addresses with move for this game round.
+        self.addresses_with_move(game_id).insert(caller.clone());
+
+        self.record_move_payment(game_id, &caller, &payment);
+    }
+
+    // Function used to commit a hidden move in commit-reveal mode.
+    // The hash is the keccak256 of the player address, the move and a secret salt (see getMoveHash).
+    #[payable("*")]
+    #[endpoint(commitMove)]
+    #[allow(clippy::too_many_arguments)]
+    fn commit_move(
+        &self,
+        game_id: u64,
+        #[payment_token] payment_token  : EgldOrEsdtTokenIdentifier,
+        #[payment_nonce] payment_nonce  : u64,
+        #[payment_amount] payment_amount: BigUint,
+        hash: ManagedByteArray<Self::Api, 32>,
+        opt_credit: OptionalValue<MultiValue2<EgldOrEsdtTokenIdentifier, BigUint>>,
+    ) {
+        let caller = self.blockchain().get_caller();
+
+        require!(
+            self.commit_reveal().get(),
+            ERR_NOT_COMMIT_REVEAL_MODE
+        );
+        self.require_round_open(game_id);
+
+        let payment = self.take_move_payment(&caller, payment_token, payment_nonce, payment_amount, opt_credit);
+
+        // If needed, the old commit is settled like a losing move.
+        if !self.commits(game_id, &caller).is_empty() {
+            let old_commit = self.commits(game_id, &caller).take();
+            self.settle_losing_move(game_id, &PlayerMove {
+                payment_token: old_commit.payment_token,
+                payment_nonce: 0,
+                payment_amount: old_commit.payment_amount,
+                player_move: Move::None,
+                address: caller.clone(),
+            });
+        }
+
+        self.move_committed_event(game_id, &caller, &hash, &payment);
+        self.commits(game_id, &caller).set(MoveCommit {
+            hash,
+            payment_token: payment.ticker.clone(),
+            payment_amount: payment.amount.clone(),
+        });
+        self.committed_addresses(game_id).insert(caller.clone());
+
+        self.record_move_payment(game_id, &caller, &payment);
+    }
+
+    // Function used to reveal a committed move during the reveal window following the round duration.
+    #[endpoint(revealMove)]
+    fn reveal_move(
+        &self,
+        game_id: u64,
+        player_move: Move,
+        salt: ManagedBuffer,
+    ) {
+        let caller = self.blockchain().get_caller();
+        let now = self.blockchain().get_block_timestamp();
+
+        require!(
+            self.round_status(game_id).get() == RoundStatus::Collecting
+                && now >= self.timer(game_id).get() + self.duration().get()
+                && now < self.get_round_end(game_id),
+            ERR_NOT_REVEAL_WINDOW
+        );
+        require!(
+            !self.commits(game_id, &caller).is_empty(),
+            ERR_NO_COMMIT
+        );
+        let commit = self.commits(game_id, &caller).get();
+        require!(
+            self.get_move_hash(&caller, &player_move, &salt) == commit.hash,
+            ERR_COMMIT_MISMATCH
+        );
+
+        // The revealed move must still be valid.
+        let jump_size = self.get_jump_size(&commit.payment_token, &commit.payment_amount);
+        self.check_move(game_id, jump_size, player_move.clone());
+
+        self.commits(game_id, &caller).clear();
+        self.committed_addresses(game_id).swap_remove(&caller);
+
+        self.move_registered_event(
+            game_id,
+            &caller,
+            &player_move,
+            jump_size,
+            &PaymentToken {
+                ticker: commit.payment_token.clone(),
+                nonce: 0,
+                amount: commit.payment_amount.clone(),
+            },
+        );
+        self.player_move(game_id, &caller).set(
+            PlayerMove {
+                payment_token: commit.payment_token,
+                payment_nonce: 0,
+                payment_amount: commit.payment_amount,
+                player_move,
+                address: caller.clone(),
+            }
+        );
+        self.addresses_with_move(game_id).insert(caller);
+    }
+
+    // Hash of a move committed in commit-reveal mode: keccak256 of the player address, the encoded move and the salt.
+    #[view(getMoveHash)]
+    fn get_move_hash(
+        &self,
+        address: &ManagedAddress,
+        player_move: &Move,
+        salt: &ManagedBuffer,
+    ) -> ManagedByteArray<Self::Api, 32> {
+        let mut data = address.as_managed_buffer().clone();
+        let _ = player_move.dep_encode(&mut data);
+        data.append(salt);
+        self.crypto().keccak256(data)
+    }
+
+    // Function used to check that moves can be registered in the game session.
+    fn require_round_open(&self, game_id: u64) {
         // Check contract status.
         require!(
             self.status().get() == Status::Public,
@@ -97,235 +445,538 @@ pub trait Main:
         );
         // Check game session status.
         require!(
-            self.game_status().get() != GameStatus::End, 
+            self.games().contains(&game_id),
+            ERR_GAME_NOT_FOUND
+        );
+        require!(
+            self.game_status(game_id).get() != GameStatus::End, 
             ERR_GAME_ENDED
         );
+        // The maze must be fully set, not half uploaded.
+        require!(
+            self.game_status(game_id).get() == GameStatus::Start,
+            ERR_GAME_NOT_STARTED
+        );
+        // Moves can't be registered once the round duration is over, until the round is resolved.
+        require!(
+            self.round_status(game_id).get() != RoundStatus::Resolving,
+            ERR_ROUND_RESOLVING
+        );
+        let now = self.blockchain().get_block_timestamp();
+        require!(
+            self.round_status(game_id).get() != RoundStatus::Collecting || now - self.timer(game_id).get() < self.duration().get(),
+            ERR_ROUND_CLOSED
+        );
+    }
 
-        // Check payment token.
+    // Function used to check the payment of a move and get the token and amount paid, credit included.
+    // The credit spent, in the same token as the payment, is taken from the caller's credit.
+    fn take_move_payment(
+        &self,
+        caller: &ManagedAddress,
+        payment_token: EgldOrEsdtTokenIdentifier,
+        payment_nonce: u64,
+        payment_amount: BigUint,
+        opt_credit: OptionalValue<MultiValue2<EgldOrEsdtTokenIdentifier, BigUint>>,
+    ) -> PaymentToken<Self::Api> {
+        // Credit of the caller spent on this move.
+        let (credit_token, credit) = match opt_credit.into_option() {
+            Some(credit) => credit.into_tuple(),
+            None => (payment_token.clone(), BigUint::zero()),
+        };
+
+        // Check payment token, the move can be paid with credit only.
+        let token = if payment_amount > 0 {
+            require!(
+                payment_nonce == 0,
+                ERR_PAYMENT_NONCE
+            );
+            require!(
+                credit == 0 || credit_token == payment_token,
+                ERR_CREDIT_TOKEN
+            );
+            payment_token
+        } else {
+            credit_token
+        };
         require!(
-            payment_token == self.payment_token().get(), 
+            self.payment_tokens().contains(&token), 
             ERR_PAYMENT_IDENTIFIER
         );
+
         require!(
-            payment_nonce == 0,
-            ERR_PAYMENT_NONCE
+            credit <= self.player_credit(caller, &token).get(),
+            ERR_NOT_ENOUGH_CREDIT
         );
+        let payment_amount = payment_amount + &credit;
 
         // Amount can vary depending on number of jumps in the move.
-        // Amount needs to be a multiple of the payment amount set in the contract.
+        // Amount needs to be a multiple of the price set in the contract for the token.
         require!(
-            payment_amount.clone() % self.payment_amount().get() == 0 && payment_amount.clone() != BigUint::zero(),
+            &payment_amount % &self.token_price(&token).get() == 0 && payment_amount != BigUint::zero(),
             ERR_PAYMENT_AMOUNT
         );
 
-        // payment_amount is a multiple of the one in memory, so jump size will always be a positive integer.
-        let jump_size = (payment_amount.clone()/self.payment_amount().get()).to_u64().unwrap() as usize;
-        
-        // Check move validity
-        self.check_move(jump_size, player_move.clone());
-
-        // If needed, remove the old move registered.
-        if self.addresses_with_move().contains(&caller) {
-            for p_move in self.moves().iter() {
-                if p_move.address == caller.clone() {
-                    self.moves().swap_remove(&p_move);
-                }
-            }
-        }
-
-        // Insert the new player move in this game's round registered moves.
-        self.moves().insert(
-            PlayerMove {
-                payment_token: payment_token,
-                payment_nonce: payment_nonce,
-                payment_amount: payment_amount.clone(),
-                player_move: player_move,
-                address: caller.clone(),
-            }
-        );
-
-        // Add address to addresses with move for this game round.
-        if !self.addresses_with_move().contains(&caller) {
-            self.addresses_with_move().insert(caller.clone());
+        self.player_credit(caller, &token).update(|val| *val -= &credit);
+        PaymentToken {
+            ticker: token,
+            nonce: 0,
+            amount: payment_amount,
         }
+    }
 
+    // Function used to record the payment of a move in the game session, the first move of a round starts it.
+    fn record_move_payment(
+        &self,
+        game_id: u64,
+        caller: &ManagedAddress,
+        payment: &PaymentToken<Self::Api>,
+    ) {
         // Add player for this entire game session.
-        if !self.players().contains(&caller) {
-            self.players().insert(caller.clone());
+        if !self.players(game_id).contains(caller) {
+            self.players(game_id).insert(caller.clone());
         }
+        self.game_tokens(game_id).insert(payment.ticker.clone());
+        self.moves_submitted(game_id, caller).update(|val| *val += 1);
 
         // Add payment amount the the caller's total payments for this game session.
-        self.player_payments(&caller).update(|val| *val += payment_amount.clone());
+        self.player_payments(game_id, caller, &payment.ticker).update(|val| *val += &payment.amount);
         // Add to the total amount collected for this game session.
-        self.collected_tokens().update(|val| *val += payment_amount);
-
-        // If this is the first move registered in this game round.
-        if !self.can_make_move().get() {
-            self.ping();
-        }
-    }
-
-    fn check_move(&self, jump_size: usize, player_move: Move) {
-        // Check for obstacles on the way
-        for jump in 1..=jump_size {
-            // Get the position of the player if the player move is applied, block by block until the player jumps "jump_size" blocks.
-            let new_move = self.get_new_position(player_move.clone(), jump);
-
-            // Get each block on the way.
-            let block = self.matrix(new_move.row).get(new_move.col);
-            
-            match block {
-                Block::Wall => {
-                    require!(
-                        block != Block::Wall, 
-                        ERR_GO_THROUGH_WALL
-                    );
-                    break; // Stop checking further moves if there's a wall
-                },
-                Block::Door(door_id) => {
-                    require!(
-                        self.collected_keys_id().contains(&door_id), 
-                        ERR_KEY_NEEDED
-                    );
-                    break; // Stop checking further moves if there's a door
-                },
-                Block::Trap => {
-                    require!(
-                        jump_size <= 1, 
-                        ERR_JUMP_OVER_TRAP
-                    );
-                    break; // Stop checking further moves if there's a trap
-                },
-                _ => (), // Normal block or prize, just advance.
-            }
+        self.collected_tokens(game_id, &payment.ticker).update(|val| *val += &payment.amount);
+
+        // If this is the first move registered in this game round, the round starts now.
+        if self.round_status(game_id).get() == RoundStatus::Idle {
+            self.timer(game_id).set(self.blockchain().get_block_timestamp());
+            self.round_status(game_id).set(RoundStatus::Collecting);
+            self.ping(game_id);
         }
     }
 
-    // Function used to get the new position in the matrix if "player_move" and "jump" are applied.
-    fn get_new_position(
-        &self,
-        player_move: Move,
-        jump: usize,
-    ) -> Position {
-        let current_position = self.player_position().get();
+    fn check_move(&self, game_id: u64, jump_size: usize, player_move: Move) {
+        let position = self.player_position(game_id).get();
+        let keys = self.collected_keys_id(game_id);
+        if let Some(error) = self.find_obstacle(game_id, &position, jump_size, &player_move, |door_id| keys.contains(&door_id)) {
+            sc_panic!(error);
+        }
+    }
 
-        if player_move == Move::Up {
-            return Position{row: current_position.row-jump, col: current_position.col};
+    // Function used, in a committed maze, to pick the winning move of a round collecting moves once it is over.
+    // The round then waits, resolving, for the blocks on the path of the winning move only to be revealed.
+    fn pick_pending_winning_move(&self, game_id: u64) {
+        if self.matrix_root(game_id).is_empty() || self.round_status(game_id).get() != RoundStatus::Collecting {
+            return;
         }
-        else if player_move == Move::Right {
-            return Position{row: current_position.row, col: current_position.col+jump};
+        if !self.addresses_with_move(game_id).is_empty() {
+            self.pending_winning_move(game_id).set(self.select_move(game_id));
         }
-        else if player_move == Move::Down {
-            return Position{row: current_position.row+jump, col: current_position.col};
+        self.round_status(game_id).set(RoundStatus::Resolving);
+    }
+
+    // Function used to select a winning move through all registered moves of the round, with the selection mode.
+    fn select_move(&self, game_id: u64) -> PlayerMove<Self::Api> {
+        match self.selection_mode().get() {
+            SelectionMode::Uniform => self.pick_uniform_move(game_id),
+            SelectionMode::StakeWeighted => self.pick_stake_weighted_move(game_id),
+            SelectionMode::MostFunded => self.pick_most_funded_move(game_id),
         }
-        else {
-            return Position{row: current_position.row, col: current_position.col-jump};
+    }
+
+    // Function used to pick and apply the winning move, already picked in a committed maze.
+    // Returns the winning move and the block it lands on.
+    fn pick_move(&self, game_id: u64) -> (PlayerMove<Self::Api>, Block<Self::Api>) {
+        let winning_move = if self.pending_winning_move(game_id).is_empty() {
+            self.select_move(game_id)
+        } else {
+            self.pending_winning_move(game_id).take()
+        };
+        let cells = self.set_new_position(game_id, winning_move.clone());
+        self.picked_moves(game_id, &winning_move.address).update(|val| *val += 1);
+        self.total_picked_moves(game_id).update(|val| *val += 1);
+        self.cells_advanced(game_id, &winning_move.address).update(|val| *val += cells as u64);
+        self.round_resolved_event(
+            game_id,
+            &winning_move.address,
+            &winning_move.player_move,
+            self.get_jump_size(&winning_move.payment_token, &winning_move.payment_amount),
+            &self.player_position(game_id).get(),
+        );
+        let position = self.player_position(game_id).get();
+        let block = self.matrix(game_id, position.row).get(position.col);
+        // A blocked move leaves the player on the current block, which isn't applied again.
+        if cells > 0 {
+            self.check_block(game_id, winning_move.address.clone());
         }
+        (winning_move, block)
+    }
+
+    // Every registered move has the same chance to win.
+    fn pick_uniform_move(&self, game_id: u64) -> PlayerMove<Self::Api> {
+        let mut rand_source = RandomnessSource::new();
+        let rand_index = rand_source.next_usize_in_range(1, self.addresses_with_move(game_id).len()+1);
+        self.get_move_by_index(game_id, rand_index)
     }
 
-    // Function used to pick a random winning move through all registered moves in the moves() mapper.
-    fn pick_move(&self) -> ManagedAddress {
+    // The chance to win of a move is proportional to its payment value, converted with the token weights.
+    // Moves whose payments are all worth nothing have the same chance to win.
+    fn pick_stake_weighted_move(&self, game_id: u64) -> PlayerMove<Self::Api> {
+        let mut total_weight = BigUint::zero();
+        for address in self.addresses_with_move(game_id).iter() {
+            let p_move = self.player_move(game_id, &address).get();
+            total_weight += self.get_weighted_value(&p_move.payment_token, &p_move.payment_amount);
+        }
+        if total_weight == 0 {
+            return self.pick_uniform_move(game_id);
+        }
+
         let mut rand_source = RandomnessSource::new();
-        let rand_index = rand_source.next_usize_in_range(1, self.moves().len()+1);
-        let winning_move = self.moves().get_by_index(rand_index);
-        self.set_new_position(winning_move.clone());
-        self.check_block(winning_move.address.clone());
-        return winning_move.address;
+        let mut roll = BigUint::from_bytes_be_buffer(&rand_source.next_bytes(32)) % &total_weight;
+        for address in self.addresses_with_move(game_id).iter() {
+            let p_move = self.player_move(game_id, &address).get();
+            let weight = self.get_weighted_value(&p_move.payment_token, &p_move.payment_amount);
+            if roll < weight {
+                return p_move;
+            }
+            roll -= weight;
+        }
+        self.get_move_by_index(game_id, self.addresses_with_move(game_id).len())
+    }
+
+    // The move option (direction and jump) with the most payment tokens wins.
+    // Ties go to the option registered first, the winning address is the biggest contributor to the option.
+    fn pick_most_funded_move(&self, game_id: u64) -> PlayerMove<Self::Api> {
+        let mut best_total = MoveTotal {
+            player_move: Move::None,
+            jump: 0,
+            amount: BigUint::zero(),
+        };
+        for total in self.get_move_totals(game_id).iter() {
+            if total.amount > best_total.amount {
+                best_total = total;
+            }
+        }
+
+        let mut winning_move = self.get_move_by_index(game_id, 1);
+        let mut best_amount = BigUint::zero();
+        for address in self.addresses_with_move(game_id).iter() {
+            let p_move = self.player_move(game_id, &address).get();
+            if p_move.player_move == best_total.player_move
+                && self.get_jump_size(&p_move.payment_token, &p_move.payment_amount) == best_total.jump
+                && self.get_weighted_value(&p_move.payment_token, &p_move.payment_amount) > best_amount
+            {
+                best_amount = self.get_weighted_value(&p_move.payment_token, &p_move.payment_amount);
+                winning_move = p_move;
+            }
+        }
+        winning_move
     }
 
     // Function used to set the new player position.
+    // Returns the number of blocks the player moved forward.
     fn set_new_position(
         &self,
+        game_id: u64,
         player_move: PlayerMove<Self::Api>,
-    ) 
+    ) -> usize
     {
         // Set the player coordinates to the new correct coordinates.
-        let movement = player_move.player_move;
-        let current_position = self.player_position().get();
-        let jump = (player_move.payment_amount/self.payment_amount().get()).to_u64().unwrap() as usize;
-        if movement == Move::Up {
-            self.player_position().set(Position{row: current_position.row-jump, col: current_position.col});
+        let current_position = self.player_position(game_id).get();
+        let jump = self.get_jump_size(&player_move.payment_token, &player_move.payment_amount);
+        // The player stays in place if the move is blocked, the maze edge included.
+        let keys = self.collected_keys_id(game_id);
+        if self.find_obstacle(game_id, &current_position, jump, &player_move.player_move, |door_id| keys.contains(&door_id)).is_some() {
+            return 0;
         }
-        else if movement == Move::Right {
-            self.player_position().set(Position{row: current_position.row, col: current_position.col+jump});
+        self.player_position(game_id).set(self.get_new_position(&current_position, &player_move.player_move, jump));
+        jump
+    }
+
+    // Function used to apply the losing moves policy to a move that won't be played.
+    fn settle_losing_move(
+        &self,
+        game_id: u64,
+        p_move: &PlayerMove<Self::Api>,
+    ) {
+        let amount = match self.losing_move_policy().get() {
+            LosingMovePolicy::Keep => return,
+            LosingMovePolicy::Refund => &p_move.payment_amount * &self.refund_percent().get() / MULTIPLIER_TOTAL,
+            LosingMovePolicy::Credit => p_move.payment_amount.clone(),
+        };
+        if amount == 0 {
+            return;
         }
-        else if movement == Move::Down {
-            self.player_position().set(Position{row: current_position.row+jump, col: current_position.col});
+
+        // The amount no longer counts as collected or paid by the player.
+        self.collected_tokens(game_id, &p_move.payment_token).update(|val| *val -= &amount);
+        self.player_payments(game_id, &p_move.address, &p_move.payment_token).update(|val| *val -= &amount);
+
+        if self.losing_move_policy().get() == LosingMovePolicy::Refund {
+            self.send()
+                    .direct(&p_move.address, &p_move.payment_token, 0, &amount);
+        } else {
+            self.player_credit(&p_move.address, &p_move.payment_token).update(|val| *val += amount);
         }
-        else if movement == Move::Left {
-            self.player_position().set(Position{row: current_position.row, col: current_position.col-jump});
+    }
+
+    // Function used to apply the unrevealed commits policy to the commits left at the end of the round.
+    fn settle_unrevealed_commits(&self, game_id: u64) {
+        for address in self.committed_addresses(game_id).iter() {
+            let commit = self.commits(game_id, &address).take();
+            if self.unrevealed_policy().get() == UnrevealedPolicy::Refund {
+                self.refund_payment(game_id, &address, &commit.payment_token, &commit.payment_amount);
+            }
         }
+        self.committed_addresses(game_id).clear();
+    }
+
+    // Function used to send back "amount" of "token" paid by a player, the amount no longer counting as collected or paid by the player.
+    fn refund_payment(
+        &self,
+        game_id: u64,
+        address: &ManagedAddress,
+        token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    ) {
+        self.collected_tokens(game_id, token).update(|val| *val -= amount);
+        self.player_payments(game_id, address, token).update(|val| *val -= amount);
+        self.send()
+                .direct(address, token, 0, amount);
+    }
+
+    // Function used by players to claim their credit in "token" from losing moves.
+    #[endpoint(claimCredit)]
+    fn claim_credit(&self, token: EgldOrEsdtTokenIdentifier) {
+        let caller = self.blockchain().get_caller();
+        let credit = self.player_credit(&caller, &token).take();
+        require!(
+            credit > 0,
+            ERR_NO_CREDIT
+        );
+        self.send()
+                .direct(&caller, &token, 0, &credit);
     }
 
     // Function used to check and handle the current block.
     fn check_block(
         &self,
+        game_id: u64,
         caller: ManagedAddress
     ) {
-        let current_position = self.player_position().get();
-        let block = self.matrix(current_position.row).get(current_position.col);
+        let current_position = self.player_position(game_id).get();
+        let block = self.matrix(game_id, current_position.row).get(current_position.col);
+        self.visited_cells(game_id).insert(current_position);
         // At this point player position should never be a Wall.
         require!(
             block != Block::Wall, 
             ERR_GO_THROUGH_WALL
         );
         match block {
-            Block::Prize(prize_info) => self.handle_reward(prize_info, caller),
-            Block::Trap => self.handle_trap(),
-            Block::Door(door_id) => self.handle_door(door_id),
-            Block::Key(key_id) => self.handle_key(key_id),
-            //Block::Random => self.handle_random(),
-            //Block::Teleport => self.handle_teleport(),
+            Block::Prize(prize_info) => self.handle_reward(game_id, prize_info, caller),
+            Block::Trap => self.handle_trap(game_id, caller),
+            Block::Door(door_id) => self.handle_door(game_id, door_id, caller),
+            Block::Key(key_id) => self.handle_key(game_id, key_id, caller),
+            Block::Random(table_id) => self.handle_random(game_id, table_id, caller),
+            Block::Teleport(pair_id) => self.handle_teleport(game_id, pair_id),
             _=> (), // Normal block, just advance.
         }
+        // The player may have been moved by the block.
+        self.visited_cells(game_id).insert(self.player_position(game_id).get());
     }
 
     // Function used to handle move on a door block.
     fn handle_door(
         &self,
-        door_id: u64
+        game_id: u64,
+        door_id: u64,
+        caller: ManagedAddress,
     ) {
         require!(
-            self.collected_keys_id().contains(&door_id), 
+            self.collected_keys_id(game_id).contains(&door_id), 
             ERR_KEY_NEEDED
         );
+        self.door_opened_event(game_id, &caller, door_id, &self.player_position(game_id).get());
     }
 
     // Function used to handle move on a key block.
     fn handle_key(
         &self,
-        key_id: u64
+        game_id: u64,
+        key_id: u64,
+        caller: ManagedAddress,
+    ) {
+        let current_position = self.player_position(game_id).get();
+        self.matrix(game_id, current_position.row).set(current_position.col, &Block::None);
+        if !self.collected_keys_id(game_id).contains(&key_id) {
+            self.collected_keys_id(game_id).insert(key_id);
+        }
+        self.keys_collected(game_id, &caller).update(|val| *val += 1);
+        self.key_collected_event(game_id, &caller, key_id, &current_position);
+    }
+
+    // Function used to handle move on a random block.
+    // An outcome is rolled from the outcome table, outcomes that can't be applied result in no effect.
+    // A block without outcome table, like a block of a committed maze revealed before its table is set, has no effect.
+    fn handle_random(
+        &self,
+        game_id: u64,
+        table_id: u64,
+        caller: ManagedAddress,
+    ) {
+        if self.random_table(table_id).is_empty() {
+            return;
+        }
+        let position = self.player_position(game_id).get();
+        let table = self.random_table(table_id).get();
+        let total_weight = table.back_to_start + table.random_move + table.random_key + table.bonus;
+        if total_weight == 0 {
+            return;
+        }
+
+        let mut rand_source = RandomnessSource::new();
+        let mut roll = rand_source.next_u64_in_range(0, total_weight);
+        let mut outcome = if roll < table.back_to_start {
+            RandomOutcome::BackToStart
+        } else {
+            roll -= table.back_to_start;
+            if roll < table.random_move {
+                RandomOutcome::RandomMove
+            } else if roll - table.random_move < table.random_key {
+                RandomOutcome::RandomKey
+            } else {
+                RandomOutcome::Bonus
+            }
+        };
+
+        let applied = match outcome {
+            RandomOutcome::BackToStart => {
+                self.player_position(game_id).set(self.start_position(game_id).get());
+                true
+            },
+            RandomOutcome::RandomMove => self.roll_random_move(game_id, &mut rand_source, table.move_jump),
+            RandomOutcome::RandomKey => self.roll_random_key(game_id, &mut rand_source),
+            RandomOutcome::Bonus => self.pay_bonus(&caller, &table.bonus_token, table.bonus_amount),
+            RandomOutcome::None => false,
+        };
+        if !applied {
+            outcome = RandomOutcome::None;
+        }
+        if outcome == RandomOutcome::RandomKey {
+            self.keys_collected(game_id, &caller).update(|val| *val += 1);
+        }
+        self.random_outcome_event(game_id, &caller, table_id, &position, &outcome);
+
+        // The player lands on a new block after a random move.
+        if outcome == RandomOutcome::RandomMove {
+            self.check_block(game_id, caller);
+        }
+    }
+
+    // Function used to move the player "jump" blocks in a random direction without obstacles on the way.
+    // In a committed maze, the blocks on the way must also be revealed.
+    fn roll_random_move(
+        &self,
+        game_id: u64,
+        rand_source: &mut RandomnessSource<Self::Api>,
+        jump: usize,
+    ) -> bool {
+        let position = self.player_position(game_id).get();
+        let keys = self.collected_keys_id(game_id);
+        let mut legal_moves: ManagedVec<Move> = ManagedVec::new();
+        for direction in [Move::Up, Move::Right, Move::Down, Move::Left] {
+            if jump > 0
+                && self.find_obstacle(game_id, &position, jump, &direction, |door_id| keys.contains(&door_id)).is_none()
+                && self.is_path_revealed(game_id, &position, jump, &direction)
+            {
+                legal_moves.push(direction);
+            }
+        }
+        if legal_moves.is_empty() {
+            return false;
+        }
+        let direction = legal_moves.get(rand_source.next_usize_in_range(0, legal_moves.len()));
+        self.player_position(game_id).set(self.get_new_position(&position, &direction, jump));
+        true
+    }
+
+    // Function used to grant a random key of the maze that hasn't been collected yet.
+    fn roll_random_key(
+        &self,
+        game_id: u64,
+        rand_source: &mut RandomnessSource<Self::Api>,
+    ) -> bool {
+        let mut missing_keys: ManagedVec<u64> = ManagedVec::new();
+        for key_id in self.maze_keys_id(game_id).iter() {
+            if !self.collected_keys_id(game_id).contains(&key_id) {
+                missing_keys.push(key_id);
+            }
+        }
+        if missing_keys.is_empty() {
+            return false;
+        }
+        let key_id = missing_keys.get(rand_source.next_usize_in_range(0, missing_keys.len()));
+        self.collected_keys_id(game_id).insert(key_id);
+        true
+    }
+
+    // Function used to pay a bonus from the bonus pool, limited to what is left in the pool.
+    fn pay_bonus(
+        &self,
+        caller: &ManagedAddress,
+        bonus_token: &EgldOrEsdtTokenIdentifier,
+        bonus_amount: BigUint,
+    ) -> bool {
+        let amount = core::cmp::min(bonus_amount, self.bonus_pool(bonus_token).get());
+        if amount == 0 {
+            return false;
+        }
+        self.bonus_pool(bonus_token).update(|val| *val -= &amount);
+        self.send()
+                .direct(caller, bonus_token, 0, &amount);
+        true
+    }
+
+    // Function used to handle move on a teleport block.
+    fn handle_teleport(
+        &self,
+        game_id: u64,
+        pair_id: u64
     ) {
-        let current_position = self.player_position().get();
-        self.matrix(current_position.row).set(current_position.col, &Block::None);
-        if !self.collected_keys_id().contains(&key_id) {
-            self.collected_keys_id().insert(key_id);
+        // Player is moved to the other teleport sharing the same pair id.
+        let current_position = self.player_position(game_id).get();
+        for position in self.teleports(game_id, pair_id).iter() {
+            if position != current_position {
+                self.player_position(game_id).set(position);
+            }
         }
     }
 
     // Function used to handle move on a trap block.
     fn handle_trap(
-        &self
+        &self,
+        game_id: u64,
+        caller: ManagedAddress,
     ) {
+        self.trap_triggered_event(game_id, &caller, &self.player_position(game_id).get());
+        self.traps_triggered(game_id, &caller).update(|val| *val += 1);
         // Player position is reset to the start position.
-        self.player_position().set(self.start_position().get());
+        self.player_position(game_id).set(self.start_position(game_id).get());
     }
 
     // Function used to handle move on a prize block.
     fn handle_reward(
         &self,
+        game_id: u64,
         prize: PrizeInfo<Self::Api>,
         caller: ManagedAddress,
     ) {
         // Give reward then clear reward case.
         if prize.prize_type == PrizeType::Finish {
-            self.game_status().set(GameStatus::End);
+            self.game_status(game_id).set(GameStatus::End);
         } 
-        let position = self.player_position().get();
-        self.matrix(position.row).set(position.col, &Block::None);
+        let position = self.player_position(game_id).get();
+        self.matrix(game_id, position.row).set(position.col, &Block::None);
 
         // Remove old prize entry.
-        self.prizes_to_win().swap_remove(&PrizeToWin{
+        self.prizes_to_win(game_id).swap_remove(&PrizeToWin{
             ticker: prize.ticker.clone(),
             nonce: prize.nonce,
             amount: prize.quantity.clone(),
@@ -333,7 +984,7 @@ pub trait Main:
         });
 
         // Enter new prize entry with winner address.
-        self.prizes_to_win().insert(PrizeToWin{
+        self.prizes_to_win(game_id).insert(PrizeToWin{
             ticker: prize.ticker.clone(),
             nonce: prize.nonce,
             amount: prize.quantity.clone(),
@@ -343,37 +994,63 @@ pub trait Main:
         // Send reward to winner.
         self.send()
                 .direct(&caller, &prize.ticker, prize.nonce, &prize.quantity);
+        self.prize_won_event(game_id, &caller, &position, &prize);
+        self.prizes_collected(game_id, &caller).update(|val| *val += 1);
+
+        if prize.prize_type == PrizeType::Finish {
+            // The player reaching the finish also wins the jackpot of the game session.
+            for payment in self.game_jackpot(game_id).take().iter() {
+                self.send()
+                        .direct(&caller, &payment.ticker, payment.nonce, &payment.amount);
+                self.jackpot_won_event(game_id, &caller, &payment);
+            }
+        }
     }
 
-    // Function used by the MVP to claim the rewards share.
-    #[endpoint(mvpClaim)]
-    fn mvp_claim(&self) -> SCResult<()>{
+    // Function used by the player at "rank" of the leaderboard to claim the rewards share of the rank, ranks starting at 1.
+    #[endpoint(claimRankReward)]
+    fn claim_rank_reward(&self, game_id: u64, rank: usize) -> SCResult<()>{
 
-        // MVP rewards must not be already claimed.
+        // Rank rewards must not be already claimed.
         require!(
-            self.mvp_claimed().get() == false, 
-            ERR_MVP_CLAIMED
+            !self.rank_claimed(game_id, rank).get(),
+            ERR_RANK_CLAIMED
         );
 
         // The whole game session must have ended.
         require!(
-            self.game_status().get() == GameStatus::End, 
+            self.game_status(game_id).get() == GameStatus::End, 
             ERR_GAME_ONGOING
         );
 
-        let caller = self.blockchain().get_caller();
-        let mvp_info = self.get_mvp();
+        let percent = self.get_rank_percent(game_id, rank);
+        require!(
+            percent > 0,
+            ERR_NO_RANK_REWARD
+        );
 
-        // Caller must be the MVP.
+        // Caller must be the player at this rank.
+        let caller = self.blockchain().get_caller();
+        let leaderboard = self.get_leaderboard(game_id, rank);
         require!(
-            caller == mvp_info.address, 
-            ERR_NOT_MVP
+            leaderboard.len() == rank && leaderboard.get(rank - 1).address == caller,
+            ERR_NOT_AT_RANK
         );
-        // Send the share to the MVP.
-        let amount = self.collected_tokens().get() * self.mvp_percent().get() / MULTIPLIER_TOTAL;
-        self.send()
-                .direct(&caller, &self.payment_token().get(), 0, &amount);
-        self.mvp_claimed().set(true);
+        // Send the share of each payment token collected to the player.
+        for token in self.game_tokens(game_id).iter() {
+            let amount = self.get_share_amount(game_id, &token, &percent);
+            if amount == 0 {
+                continue;
+            }
+            self.send()
+                    .direct(&caller, &token, 0, &amount);
+            self.rank_reward_claimed_event(game_id, &caller, rank, &PaymentToken {
+                ticker: token,
+                nonce: 0,
+                amount,
+            });
+        }
+        self.rank_claimed(game_id, rank).set(true);
         Ok(())
     }
 }
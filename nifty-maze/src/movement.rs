@@ -0,0 +1,114 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::errors::{
+    ERR_GO_THROUGH_WALL,
+    ERR_KEY_NEEDED,
+    ERR_JUMP_OVER_TRAP,
+    ERR_OUT_OF_BOUNDS,
+};
+
+use crate::storage::{
+    Block,
+    Dimensions,
+    Move,
+    Position,
+};
+
+// Movement rules shared by the move checks, the round resolution and the maze solver.
+#[multiversx_sc::module]
+pub trait MovementModule:
+    crate::storage::StorageModule
+{
+    // Function used to find the first obstacle preventing the move from "from", returns the matching error if any.
+    // "has_key" tells if the key opening a door has been collected.
+    fn find_obstacle<F: Fn(u64) -> bool>(
+        &self,
+        game_id: u64,
+        from: &Position,
+        jump_size: usize,
+        player_move: &Move,
+        has_key: F,
+    ) -> Option<&'static str> {
+        let dimensions = self.matrix_dimensions(game_id).get();
+        // Check for obstacles on the way
+        for jump in 1..=jump_size {
+            // The maze edge behaves like a wall.
+            if !self.fits_in_maze(&dimensions, from, player_move, jump) {
+                return Some(ERR_OUT_OF_BOUNDS);
+            }
+
+            // Get the position of the player if the player move is applied, block by block until the player jumps "jump_size" blocks.
+            let new_move = self.get_new_position(from, player_move, jump);
+
+            // Get each block on the way.
+            let block = self.matrix(game_id, new_move.row).get(new_move.col);
+
+            match block {
+                Block::Wall => {
+                    return Some(ERR_GO_THROUGH_WALL);
+                },
+                Block::Door(door_id) => {
+                    // An open door doesn't stop the checks of the blocks behind it.
+                    if !has_key(door_id) {
+                        return Some(ERR_KEY_NEEDED);
+                    }
+                },
+                Block::Trap => {
+                    if jump_size > 1 {
+                        return Some(ERR_JUMP_OVER_TRAP);
+                    }
+                    break; // Stop checking further moves if there's a trap
+                },
+                _ => (), // Normal block or prize, just advance.
+            }
+        }
+        None
+    }
+
+    // Function used to know if the position reached from "from" by "player_move" and "jump" is still inside the maze.
+    fn fits_in_maze(
+        &self,
+        dimensions: &Dimensions,
+        from: &Position,
+        player_move: &Move,
+        jump: usize,
+    ) -> bool {
+        if !self.is_in_maze(dimensions, from) {
+            return false;
+        }
+        match player_move {
+            Move::Up => jump < from.row,
+            Move::Right => jump <= dimensions.cols - from.col,
+            Move::Down => jump <= dimensions.rows - from.row,
+            Move::Left => jump < from.col,
+            Move::None => false,
+        }
+    }
+
+    // Function used to know if "position" is a block of the maze, rows and columns starting at 1.
+    fn is_in_maze(
+        &self,
+        dimensions: &Dimensions,
+        position: &Position,
+    ) -> bool {
+        position.row >= 1 && position.row <= dimensions.rows && position.col >= 1 && position.col <= dimensions.cols
+    }
+
+    // Function used to get the new position in the matrix if "player_move" and "jump" are applied from "from".
+    // The move must fit in the maze.
+    fn get_new_position(
+        &self,
+        from: &Position,
+        player_move: &Move,
+        jump: usize,
+    ) -> Position {
+        match player_move {
+            Move::Up => Position{row: from.row - jump, col: from.col},
+            Move::Right => Position{row: from.row, col: from.col + jump},
+            Move::Down => Position{row: from.row + jump, col: from.col},
+            Move::Left => Position{row: from.row, col: from.col - jump},
+            Move::None => from.clone(),
+        }
+    }
+}
@@ -0,0 +1,145 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::errors::{
+    ERR_GAME_ONGOING,
+    ERR_SHARE_DISTRIBUTED,
+    ERR_NO_TREASURY_ADDRESS,
+    ERR_NO_ROYALTY_ADDRESS,
+    ERR_PATHFINDER_CLAIMED,
+    ERR_NO_PICKED_MOVE,
+};
+
+use crate::storage::{
+    GameStatus,
+    PaymentToken,
+    RevenueShare,
+};
+
+#[multiversx_sc::module]
+pub trait RevenueModule:
+    crate::storage::StorageModule
+    + crate::validation::ValidationModule
+    + crate::events::EventsModule
+{
+    // Function used by anyone to send the treasury share of an ended game session to the treasury address.
+    #[endpoint(distributeTreasury)]
+    fn distribute_treasury(&self, game_id: u64) {
+        require!(
+            !self.treasury_address().is_empty(),
+            ERR_NO_TREASURY_ADDRESS
+        );
+        let percent = self.game_revenue_split(game_id).get().treasury;
+        self.distribute_share(game_id, RevenueShare::Treasury, &self.treasury_address().get(), &percent);
+    }
+
+    // Function used by anyone to send the royalty share of an ended game session to the creator of the maze.
+    #[endpoint(distributeRoyalty)]
+    fn distribute_royalty(&self, game_id: u64) {
+        require!(
+            !self.royalty_address(game_id).is_empty(),
+            ERR_NO_ROYALTY_ADDRESS
+        );
+        let percent = self.game_revenue_split(game_id).get().royalty;
+        self.distribute_share(game_id, RevenueShare::Royalty, &self.royalty_address(game_id).get(), &percent);
+    }
+
+    // Function used by anyone to roll the jackpot share of an ended game session over into the next game session.
+    // The shares of the leaderboard ranks left without player are added to the jackpot.
+    #[endpoint(distributeJackpot)]
+    fn distribute_jackpot(&self, game_id: u64) {
+        let percent = self.game_revenue_split(game_id).get().jackpot + self.get_unfilled_ranks_percent(game_id);
+        self.distribute_share(game_id, RevenueShare::Jackpot, &self.blockchain().get_sc_address(), &percent);
+    }
+
+    // Function used by the players whose moves got picked to claim their share of the pathfinder pool once the game session has ended.
+    // The pool is split in proportion to the number of picked moves of each player.
+    #[endpoint(claimPathfinderReward)]
+    fn claim_pathfinder_reward(&self, game_id: u64) {
+        require!(
+            self.game_status(game_id).get() == GameStatus::End,
+            ERR_GAME_ONGOING
+        );
+        let caller = self.blockchain().get_caller();
+        require!(
+            !self.pathfinder_claimed(game_id, &caller).get(),
+            ERR_PATHFINDER_CLAIMED
+        );
+        let picked_moves = self.picked_moves(game_id, &caller).get();
+        require!(
+            picked_moves > 0,
+            ERR_NO_PICKED_MOVE
+        );
+        self.pathfinder_claimed(game_id, &caller).set(true);
+
+        let total_picked_moves = self.total_picked_moves(game_id).get();
+        for token in self.pathfinder_tokens(game_id).iter() {
+            let amount = self.pathfinder_pool(game_id, &token).get() * picked_moves / total_picked_moves;
+            if amount == 0 {
+                continue;
+            }
+            self.send()
+                    .direct(&caller, &token, 0, &amount);
+            self.pathfinder_reward_claimed_event(game_id, &caller, &PaymentToken {
+                ticker: token,
+                nonce: 0,
+                amount,
+            });
+        }
+    }
+
+    // Function used to pay the share of each payment token collected, the jackpot share staying in the contract.
+    fn distribute_share(
+        &self,
+        game_id: u64,
+        share: RevenueShare,
+        address: &ManagedAddress,
+        percent: &BigUint,
+    ) {
+        // The whole game session must have ended.
+        require!(
+            self.game_status(game_id).get() == GameStatus::End,
+            ERR_GAME_ONGOING
+        );
+        require!(
+            !self.share_distributed(game_id, &share).get(),
+            ERR_SHARE_DISTRIBUTED
+        );
+        self.share_distributed(game_id, &share).set(true);
+
+        let mut shares: ManagedVec<PaymentToken<Self::Api>> = ManagedVec::new();
+        for token in self.game_tokens(game_id).iter() {
+            let amount = self.get_share_amount(game_id, &token, percent);
+            if amount == 0 {
+                continue;
+            }
+            if share != RevenueShare::Jackpot {
+                self.send()
+                        .direct(address, &token, 0, &amount);
+            }
+            let payment = PaymentToken {
+                ticker: token,
+                nonce: 0,
+                amount,
+            };
+            self.share_distributed_event(game_id, address, &share, &payment);
+            shares.push(payment);
+        }
+
+        if share == RevenueShare::Jackpot {
+            self.add_to_jackpot(&shares);
+        }
+    }
+
+    // Function used to add payment tokens to the jackpot of the next game session.
+    fn add_to_jackpot(
+        &self,
+        payments: &ManagedVec<PaymentToken<Self::Api>>,
+    ) {
+        let mut jackpot = self.jackpot().get();
+        for payment in payments.iter() {
+            self.add_prize_amount(&mut jackpot, payment.ticker, payment.nonce, payment.amount);
+        }
+        self.jackpot().set(jackpot);
+    }
+}
@@ -1,29 +1,89 @@
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
+use crate::MULTIPLIER_TOTAL;
+
+use crate::errors::{
+    ERR_GAME_NOT_FOUND,
+    ERR_GAME_NOT_STARTED,
+    ERR_PAYMENT_IDENTIFIER,
+    ERR_PAYMENT_ZERO,
+    ERR_PRICE_ZERO,
+    ERR_OUT_OF_BOUNDS,
+    ERR_ROUND_NOT_IDLE,
+    ERR_SPLIT_TOTAL,
+    ERR_REFUND_PERCENT,
+    ERR_SPLIT_NOT_SET,
+    ERR_MATRIX_NOT_SET,
+    ERR_MATRIX_NOT_EDITABLE,
+    ERR_MATRIX_ALREADY_SET,
+    ERR_MAZE_UPLOAD_ONGOING,
+    ERR_NO_MAZE_UPLOAD,
+    ERR_CHUNK_ORDER,
+    ERR_MAZE_UPLOAD_INCOMPLETE,
+    ERR_COMMITTED_MAZE,
+    ERR_NO_REVEAL_TIMEOUT,
+    ERR_CURVE_TOTAL,
+};
+
 use crate::storage::{
     Block,
     Position,
     GameStatus,
-    PrizeToWin,
+    Dimensions,
+    RandomTable,
+    RevenueSplit,
+    RevenueShare,
+    RoundStatus,
+    SelectionMode,
+    LosingMovePolicy,
+    UnrevealedPolicy,
     Status,
 };
 
 #[multiversx_sc::module]
 pub trait SetupModule:
     crate::storage::StorageModule 
+    + crate::validation::ValidationModule
+    + crate::movement::MovementModule
+    + crate::events::EventsModule
+    + crate::revenue::RevenueModule
+    + crate::history::HistoryModule
 {
-    // Function used to register the ESDT used as payment by users to take part in the game.
+    // Function used to register a token accepted as payment by users to take part in the game.
+    // "price" is the price of a jump, "weight" converts the payments to compare them with the other tokens (MULTIPLIER_TOTAL = 1).
     #[only_owner]
     #[endpoint(registerPaymentToken)]
     #[allow(clippy::too_many_arguments)]
     fn register_payment_token(
         &self,
         token: EgldOrEsdtTokenIdentifier,
-        quantity: BigUint,
+        price: BigUint,
+        weight: BigUint,
     ) -> SCResult<()> {
-        self.payment_token().set(&token);
-        self.payment_amount().set(&quantity);
+        require!(
+            price > 0,
+            ERR_PRICE_ZERO
+        );
+        self.payment_tokens().insert(token.clone());
+        self.token_price(&token).set(&price);
+        self.token_weight(&token).set(&weight);
+        Ok(())
+    }
+
+    // Function used to stop accepting a payment token.
+    // Price and weight are kept for the moves and payments already registered with the token.
+    #[only_owner]
+    #[endpoint(removePaymentToken)]
+    #[allow(clippy::too_many_arguments)]
+    fn remove_payment_token(
+        &self,
+        token: EgldOrEsdtTokenIdentifier,
+    ) -> SCResult<()> {
+        require!(
+            self.payment_tokens().swap_remove(&token),
+            ERR_PAYMENT_IDENTIFIER
+        );
         Ok(())
     }
 
@@ -54,36 +114,371 @@ pub trait SetupModule:
         Ok(())
     }
 
+    // Function used to create a new game session, returns the id of the game.
+    #[only_owner]
+    #[endpoint(createGame)]
+    fn create_game(&self) -> u64 {
+        require!(
+            !self.revenue_split().is_empty(),
+            ERR_SPLIT_NOT_SET
+        );
+        let game_id = self.last_game_id().get() + 1;
+        self.last_game_id().set(game_id);
+        self.games().insert(game_id);
+        // The game session gets the revenue split in place and the jackpot rolled over from the previous sessions.
+        self.game_revenue_split(game_id).set(self.revenue_split().get());
+        // Without rank curve, the whole leaderboard share goes to the first rank.
+        let mut rank_curve = self.rank_curve().get();
+        if rank_curve.is_empty() {
+            rank_curve.push(BigUint::from(MULTIPLIER_TOTAL));
+        }
+        self.game_rank_curve(game_id).set(rank_curve);
+        self.game_jackpot(game_id).set(self.jackpot().take());
+        game_id
+    }
+
+    // Function used to close a game session, it ends the game and no more moves can be registered.
+    // The session data is kept until the matrix is cleared.
+    #[only_owner]
+    #[endpoint(closeGame)]
+    fn close_game(
+        &self,
+        game_id: u64,
+    ) -> SCResult<()> {
+        self.end_game(game_id);
+        require!(
+            self.games().swap_remove(&game_id),
+            ERR_GAME_NOT_FOUND
+        );
+        // Jackpot not won rolls over into the next game session.
+        self.add_to_jackpot(&self.game_jackpot(game_id).take());
+        self.game_ended_event(game_id, &self.blockchain().get_caller(), &self.get_collected_value(game_id));
+        Ok(())
+    }
+
+    // Function used to end a game session by the owner, freezing the payment tokens collected and archiving it.
+    fn end_game(&self, game_id: u64) {
+        // The moves of a pending round must be settled first, so the payment tokens collected no longer change.
+        require!(
+            self.round_status(game_id).get() == RoundStatus::Idle,
+            ERR_ROUND_NOT_IDLE
+        );
+        // A game session ended by a player reaching the finish is already archived.
+        if self.game_status(game_id).get() != GameStatus::End {
+            self.game_status(game_id).set(GameStatus::End);
+            self.snapshot_collected_tokens(game_id);
+            self.archive_game(game_id);
+        }
+    }
+
     // Function used to register the maze matrix.
     #[only_owner]
     #[endpoint(setMatrix)]
     #[allow(clippy::too_many_arguments)]
     fn set_matrix(
         &self,
+        game_id: u64,
         args: MultiValueEncoded<MultiValue3<usize, usize, Block<Self::Api>>>
     ) -> SCResult<()> {
-        self.game_status().set(GameStatus::Start);
-        self.set_can_make_move();
-        let _ = self.init_position(2, 2);
+        require!(
+            self.games().contains(&game_id),
+            ERR_GAME_NOT_FOUND
+        );
+        require!(
+            self.maze_upload(game_id).is_empty(),
+            ERR_MAZE_UPLOAD_ONGOING
+        );
+        // Setting the matrix opens the game, resetting the player position and the round, so it must be cleared first.
+        require!(
+            self.matrix_dimensions(game_id).is_empty() && self.matrix(game_id, 1).is_empty(),
+            ERR_MATRIX_ALREADY_SET
+        );
         for triple in args.into_iter(){
             let tuple = triple.into_tuple();
             let row = tuple.0;
             let block = tuple.2;
-            self.matrix(row).push(&block);
-            match block {
-                // Register prize block in memory.
-                Block::Prize(prize_info) => {
-                    let prize_to_win = PrizeToWin {
-                        ticker: prize_info.ticker,
-                        nonce: prize_info.nonce,
-                        amount: prize_info.quantity,
-                        winner: ManagedAddress::zero()  // Assuming winner is zero address initially.
-                    };
-                    self.prizes_to_win().insert(prize_to_win);
-                },
-                _ => {} // Default case to handle all other block types
-            }        
+            let col = self.matrix(game_id, row).push(&block);
+            self.register_block(game_id, row, col, block);
         }
+
+        self.open_game(game_id);
+        Ok(())
+    }
+
+    // Function used to start the upload of a maze too large for a single transaction.
+    // The game can't be played until the upload is finalized through finalizeMaze.
+    #[only_owner]
+    #[endpoint(beginMazeUpload)]
+    #[allow(clippy::too_many_arguments)]
+    fn begin_maze_upload(
+        &self,
+        game_id: u64,
+        rows: usize,
+        cols: usize,
+    ) -> SCResult<()> {
+        require!(
+            self.games().contains(&game_id),
+            ERR_GAME_NOT_FOUND
+        );
+        require!(
+            self.maze_upload(game_id).is_empty(),
+            ERR_MAZE_UPLOAD_ONGOING
+        );
+        require!(
+            self.matrix_dimensions(game_id).is_empty() && self.matrix(game_id, 1).is_empty(),
+            ERR_MATRIX_ALREADY_SET
+        );
+        self.game_status(game_id).set(GameStatus::None);
+        self.maze_upload(game_id).set(Dimensions { rows, cols });
+        Ok(())
+    }
+
+    // Function used to upload a chunk of the maze, the blocks of each row being uploaded in order.
+    #[only_owner]
+    #[endpoint(uploadMazeChunk)]
+    #[allow(clippy::too_many_arguments)]
+    fn upload_maze_chunk(
+        &self,
+        game_id: u64,
+        args: MultiValueEncoded<MultiValue3<usize, usize, Block<Self::Api>>>
+    ) -> SCResult<()> {
+        require!(
+            !self.maze_upload(game_id).is_empty(),
+            ERR_NO_MAZE_UPLOAD
+        );
+        let dimensions = self.maze_upload(game_id).get();
+        for triple in args.into_iter() {
+            let (row, col, block) = triple.into_tuple();
+            require!(
+                self.is_in_maze(&dimensions, &Position { row, col }),
+                ERR_OUT_OF_BOUNDS
+            );
+            require!(
+                col == self.matrix(game_id, row).len() + 1,
+                ERR_CHUNK_ORDER
+            );
+            self.matrix(game_id, row).push(&block);
+            self.register_block(game_id, row, col, block);
+        }
+        Ok(())
+    }
+
+    // Function used to check the uploaded maze and open the game.
+    #[only_owner]
+    #[endpoint(finalizeMaze)]
+    #[allow(clippy::too_many_arguments)]
+    fn finalize_maze(
+        &self,
+        game_id: u64,
+    ) -> SCResult<()> {
+        require!(
+            !self.maze_upload(game_id).is_empty(),
+            ERR_NO_MAZE_UPLOAD
+        );
+        let dimensions = self.maze_upload(game_id).take();
+        for row in 1..=dimensions.rows {
+            require!(
+                self.matrix(game_id, row).len() == dimensions.cols,
+                ERR_MAZE_UPLOAD_INCOMPLETE
+            );
+        }
+        self.open_game(game_id);
+        Ok(())
+    }
+
+    // Function used to commit the maze matrix through the Merkle root of its blocks instead of storing the blocks.
+    // Blocks are unknown until revealed with their Merkle proof through revealCell.
+    #[only_owner]
+    #[endpoint(setMatrixCommitment)]
+    #[allow(clippy::too_many_arguments)]
+    fn set_matrix_commitment(
+        &self,
+        game_id: u64,
+        rows: usize,
+        cols: usize,
+        start_row: usize,
+        start_col: usize,
+        root: ManagedByteArray<Self::Api, 32>,
+    ) -> SCResult<()> {
+        require!(
+            self.games().contains(&game_id),
+            ERR_GAME_NOT_FOUND
+        );
+        require!(
+            self.maze_upload(game_id).is_empty(),
+            ERR_MAZE_UPLOAD_ONGOING
+        );
+        require!(
+            self.matrix_dimensions(game_id).is_empty() && self.matrix(game_id, 1).is_empty(),
+            ERR_MATRIX_ALREADY_SET
+        );
+        // Rounds whose blocks are never revealed must be cancelled at some point.
+        require!(
+            self.cell_reveal_timeout().get() > 0,
+            ERR_NO_REVEAL_TIMEOUT
+        );
+        let dimensions = Dimensions { rows, cols };
+        let start_position = Position { row: start_row, col: start_col };
+        require!(
+            self.is_in_maze(&dimensions, &start_position),
+            ERR_OUT_OF_BOUNDS
+        );
+        self.game_status(game_id).set(GameStatus::Start);
+        self.round_status(game_id).set(RoundStatus::Idle);
+        for row in 1..=rows {
+            for _ in 0..cols {
+                self.matrix(game_id, row).push(&Block::Unknown);
+            }
+        }
+        self.matrix_dimensions(game_id).set(&dimensions);
+        self.start_position(game_id).set(&start_position);
+        self.player_position(game_id).set(&start_position);
+        self.visited_cells(game_id).insert(start_position);
+        self.matrix_root(game_id).set(&root);
+        self.game_start(game_id).set(self.blockchain().get_block_timestamp());
+        self.matrix_hash(game_id).set(root);
+        self.matrix_set_event(game_id, &self.blockchain().get_caller(), &dimensions);
+        Ok(())
+    }
+
+    // Function used to replace a block of the matrix.
+    #[only_owner]
+    #[endpoint(setBlock)]
+    #[allow(clippy::too_many_arguments)]
+    fn set_block(
+        &self,
+        game_id: u64,
+        row: usize,
+        col: usize,
+        block: Block<Self::Api>,
+    ) -> SCResult<()> {
+        self.require_matrix_editable(game_id);
+        self.require_in_maze(game_id, row, col);
+        self.matrix(game_id, row).set(col, &block);
+        self.finish_matrix_edit(game_id, self.player_position(game_id).get());
+        Ok(())
+    }
+
+    // Function used to replace several blocks of the matrix.
+    #[only_owner]
+    #[endpoint(setBlocks)]
+    #[allow(clippy::too_many_arguments)]
+    fn set_blocks(
+        &self,
+        game_id: u64,
+        args: MultiValueEncoded<MultiValue3<usize, usize, Block<Self::Api>>>
+    ) -> SCResult<()> {
+        self.require_matrix_editable(game_id);
+        for triple in args.into_iter() {
+            let (row, col, block) = triple.into_tuple();
+            self.require_in_maze(game_id, row, col);
+            self.matrix(game_id, row).set(col, &block);
+        }
+        self.finish_matrix_edit(game_id, self.player_position(game_id).get());
+        Ok(())
+    }
+
+    // Function used to insert a row of blocks before "row", the following rows moving down.
+    // "row" can be the row following the last row to append a row.
+    #[only_owner]
+    #[endpoint(insertRow)]
+    #[allow(clippy::too_many_arguments)]
+    fn insert_row(
+        &self,
+        game_id: u64,
+        row: usize,
+        blocks: MultiValueEncoded<Block<Self::Api>>,
+    ) -> SCResult<()> {
+        self.require_matrix_editable(game_id);
+        let rows = self.matrix_dimensions(game_id).get().rows;
+        require!(
+            row >= 1 && row <= rows + 1,
+            ERR_OUT_OF_BOUNDS
+        );
+        for moved_row in (row..=rows).rev() {
+            let mut next_row = self.matrix(game_id, moved_row + 1);
+            next_row.clear();
+            for block in self.matrix(game_id, moved_row).iter() {
+                next_row.push(&block);
+            }
+        }
+        self.set_row(game_id, row, blocks);
+
+        // Positions after the inserted row move down with their blocks.
+        let mut player_position = self.player_position(game_id).get();
+        if player_position.row >= row {
+            player_position.row += 1;
+        }
+        let mut visited_cells: ManagedVec<Position> = ManagedVec::new();
+        for mut position in self.visited_cells(game_id).iter() {
+            if position.row >= row {
+                position.row += 1;
+            }
+            visited_cells.push(position);
+        }
+        self.visited_cells(game_id).clear();
+        for position in visited_cells.iter() {
+            self.visited_cells(game_id).insert(position);
+        }
+        self.finish_matrix_edit(game_id, player_position);
+        Ok(())
+    }
+
+    // Function used to replace all the blocks of a row.
+    #[only_owner]
+    #[endpoint(replaceRow)]
+    #[allow(clippy::too_many_arguments)]
+    fn replace_row(
+        &self,
+        game_id: u64,
+        row: usize,
+        blocks: MultiValueEncoded<Block<Self::Api>>,
+    ) -> SCResult<()> {
+        self.require_matrix_editable(game_id);
+        require!(
+            row >= 1 && row <= self.matrix_dimensions(game_id).get().rows,
+            ERR_OUT_OF_BOUNDS
+        );
+        self.set_row(game_id, row, blocks);
+        self.finish_matrix_edit(game_id, self.player_position(game_id).get());
+        Ok(())
+    }
+
+    // Function used to migrate the round ongoing in the single maze of the previous contract versions into a game session.
+    // The payments of the migrated moves are collected by the game session, and the round goes on from its first move.
+    #[only_owner]
+    #[endpoint(migrateMoves)]
+    #[allow(clippy::too_many_arguments)]
+    fn migrate_moves(
+        &self,
+        game_id: u64,
+    ) -> SCResult<()> {
+        require!(
+            self.game_status(game_id).get() == GameStatus::Start,
+            ERR_GAME_NOT_STARTED
+        );
+        require!(
+            self.round_status(game_id).get() == RoundStatus::Idle,
+            ERR_ROUND_NOT_IDLE
+        );
+        for p_move in self.legacy_moves().iter() {
+            if !self.players(game_id).contains(&p_move.address) {
+                self.players(game_id).insert(p_move.address.clone());
+            }
+            self.game_tokens(game_id).insert(p_move.payment_token.clone());
+            self.moves_submitted(game_id, &p_move.address).update(|val| *val += 1);
+            self.player_payments(game_id, &p_move.address, &p_move.payment_token).update(|val| *val += &p_move.payment_amount);
+            self.collected_tokens(game_id, &p_move.payment_token).update(|val| *val += &p_move.payment_amount);
+            self.addresses_with_move(game_id).insert(p_move.address.clone());
+            self.player_move(game_id, &p_move.address).set(p_move);
+        }
+        if !self.addresses_with_move(game_id).is_empty() {
+            self.timer(game_id).set(self.legacy_timer().get());
+            self.round_status(game_id).set(RoundStatus::Collecting);
+        }
+        self.legacy_moves().clear();
+        self.legacy_addresses_with_move().clear();
+        self.legacy_timer().clear();
         Ok(())
     }
 
@@ -93,21 +488,70 @@ pub trait SetupModule:
     #[allow(clippy::too_many_arguments)]
     fn clear_matrix(
         &self,
+        game_id: u64,
     ) -> SCResult<()> {
-        self.moves().clear();
-        self.timer().set(0);
-        self.mvp_claimed().set(false);
-        self.collected_tokens().clear();
-        self.prizes_to_win().clear();
-        for player in self.players().iter() {
-            self.player_payments(&player).clear();
+        for address in self.addresses_with_move(game_id).iter() {
+            self.player_move(game_id, &address).clear();
         }
-        self.collected_keys_id().clear();
+        self.addresses_with_move(game_id).clear();
+        for address in self.committed_addresses(game_id).iter() {
+            self.commits(game_id, &address).clear();
+        }
+        self.committed_addresses(game_id).clear();
+        self.pending_winning_move(game_id).clear();
+        self.timer(game_id).set(0);
+        self.round_status(game_id).set(RoundStatus::Idle);
+        for rank in 1..=self.game_rank_curve(game_id).get().len() {
+            self.rank_claimed(game_id, rank).clear();
+        }
+        for share in [RevenueShare::Treasury, RevenueShare::Jackpot, RevenueShare::Royalty] {
+            self.share_distributed(game_id, &share).clear();
+        }
+        self.prizes_to_win(game_id).clear();
+        for token in self.game_tokens(game_id).iter() {
+            self.collected_tokens(game_id, &token).clear();
+            self.final_collected_tokens(game_id, &token).clear();
+            for player in self.players(game_id).iter() {
+                self.player_payments(game_id, &player, &token).clear();
+            }
+        }
+        for player in self.players(game_id).iter() {
+            self.picked_moves(game_id, &player).clear();
+            self.cells_advanced(game_id, &player).clear();
+            self.pathfinder_claimed(game_id, &player).clear();
+            self.moves_submitted(game_id, &player).clear();
+            self.traps_triggered(game_id, &player).clear();
+            self.keys_collected(game_id, &player).clear();
+            self.prizes_collected(game_id, &player).clear();
+        }
+        self.players(game_id).clear();
+        self.total_picked_moves(game_id).clear();
+        // The pool is paid to the players of this game session only, a replay gets its own pool.
+        for token in self.pathfinder_tokens(game_id).iter() {
+            self.pathfinder_pool(game_id, &token).clear();
+        }
+        self.pathfinder_tokens(game_id).clear();
+        self.rounds_played(game_id).clear();
+        self.round_records(game_id).clear();
+        self.game_start(game_id).clear();
+        self.matrix_hash(game_id).clear();
+        self.matrix_root(game_id).clear();
+        self.maze_upload(game_id).clear();
+        self.game_tokens(game_id).clear();
+        self.collected_keys_id(game_id).clear();
+        self.maze_keys_id(game_id).clear();
+        for pair_id in self.teleport_ids(game_id).iter() {
+            self.teleports(game_id, pair_id).clear();
+        }
+        self.teleport_ids(game_id).clear();
+        self.matrix_dimensions(game_id).clear();
+        self.visited_cells(game_id).clear();
         let mut row = 1;
-        while !self.matrix(row).is_empty() {
-            self.matrix(row).clear();
+        while !self.matrix(game_id, row).is_empty() {
+            self.matrix(game_id, row).clear();
             row += 1;
         }
+        self.matrix_cleared_event(game_id, &self.blockchain().get_caller());
         Ok(())
     }
 
@@ -116,20 +560,160 @@ pub trait SetupModule:
     #[allow(clippy::too_many_arguments)]
     fn set_game_status(
         &self,
+        game_id: u64,
         status: GameStatus,
     ) -> SCResult<()> {
-        self.game_status().set(status);
+        // Ending the game session goes through the same path as closeGame, the game session staying open to the shares claims.
+        if status == GameStatus::End {
+            self.end_game(game_id);
+            self.game_ended_event(game_id, &self.blockchain().get_caller(), &self.get_collected_value(game_id));
+            return Ok(());
+        }
+        self.game_status(game_id).set(status);
+        Ok(())
+    }
+
+    // Function used to set the split of the payment tokens collected, applied to the game sessions created afterwards.
+    // Shares are percents (MULTIPLIER_TOTAL = 100%) and must sum to 100%.
+    #[only_owner]
+    #[endpoint(setRevenueSplit)]
+    #[allow(clippy::too_many_arguments)]
+    fn set_revenue_split(
+        &self,
+        leaderboard: BigUint,
+        treasury: BigUint,
+        jackpot: BigUint,
+        royalty: BigUint,
+    ) -> SCResult<()> {
+        require!(
+            &leaderboard + &treasury + &jackpot + &royalty == MULTIPLIER_TOTAL,
+            ERR_SPLIT_TOTAL
+        );
+        self.revenue_split().set(RevenueSplit {
+            leaderboard,
+            treasury,
+            jackpot,
+            royalty,
+        });
         Ok(())
     }
 
+    // Function used to set the percents of the leaderboard share paid to the top spenders, from the first rank, applied to the game sessions created afterwards.
+    // Percents (MULTIPLIER_TOTAL = 100%) must sum to 100%.
     #[only_owner]
-    #[endpoint(setMvpPercent)]
+    #[endpoint(setRankCurve)]
     #[allow(clippy::too_many_arguments)]
-    fn set_mvp_percent(
+    fn set_rank_curve(
         &self,
-        percent: BigUint,
+        percents: MultiValueEncoded<BigUint>,
+    ) -> SCResult<()> {
+        let mut total = BigUint::zero();
+        let mut rank_curve: ManagedVec<BigUint> = ManagedVec::new();
+        for percent in percents.into_iter() {
+            total += &percent;
+            rank_curve.push(percent);
+        }
+        require!(
+            total == MULTIPLIER_TOTAL,
+            ERR_CURVE_TOTAL
+        );
+        self.rank_curve().set(rank_curve);
+        Ok(())
+    }
+
+    // Function used to set the address receiving the treasury share.
+    #[only_owner]
+    #[endpoint(setTreasuryAddress)]
+    #[allow(clippy::too_many_arguments)]
+    fn set_treasury_address(
+        &self,
+        address: ManagedAddress,
     ) -> SCResult<()> {
-        self.mvp_percent().set(percent);
+        self.treasury_address().set(address);
+        Ok(())
+    }
+
+    // Function used to set the address of the creator or level designer of a maze receiving the royalty share.
+    #[only_owner]
+    #[endpoint(setRoyaltyAddress)]
+    #[allow(clippy::too_many_arguments)]
+    fn set_royalty_address(
+        &self,
+        game_id: u64,
+        address: ManagedAddress,
+    ) -> SCResult<()> {
+        self.royalty_address(game_id).set(address);
+        Ok(())
+    }
+
+    // Function used to set the outcome weights of the random blocks sharing the same table id.
+    #[only_owner]
+    #[endpoint(setRandomTable)]
+    #[allow(clippy::too_many_arguments)]
+    fn set_random_table(
+        &self,
+        table_id: u64,
+        back_to_start: u64,
+        random_move: u64,
+        random_key: u64,
+        bonus: u64,
+        move_jump: usize,
+        bonus_token: EgldOrEsdtTokenIdentifier,
+        bonus_amount: BigUint,
+    ) -> SCResult<()> {
+        self.random_table(table_id).set(RandomTable {
+            back_to_start,
+            random_move,
+            random_key,
+            bonus,
+            move_jump,
+            bonus_token,
+            bonus_amount,
+        });
+        Ok(())
+    }
+
+    // Used to load the pool paying the random blocks bonuses with payment tokens.
+    #[only_owner]
+    #[payable("*")]
+    #[endpoint(fundBonusPool)]
+    fn fund_bonus_pool(
+        &self,
+        #[payment_token] payment_token  : EgldOrEsdtTokenIdentifier,
+        #[payment_amount] payment_amount: BigUint,
+    ) -> SCResult<()> {
+        require!(
+            self.payment_tokens().contains(&payment_token),
+            ERR_PAYMENT_IDENTIFIER
+        );
+        require!(
+            payment_amount > 0,
+            ERR_PAYMENT_ZERO
+        );
+        self.bonus_pool(&payment_token).update(|val| *val += payment_amount);
+        Ok(())
+    }
+
+    // Used to load the pathfinder pool of a game session, paid at the end of the game to the players whose moves got picked.
+    #[only_owner]
+    #[payable("*")]
+    #[endpoint(fundPathfinderPool)]
+    fn fund_pathfinder_pool(
+        &self,
+        game_id: u64,
+        #[payment_token] payment_token  : EgldOrEsdtTokenIdentifier,
+        #[payment_amount] payment_amount: BigUint,
+    ) -> SCResult<()> {
+        require!(
+            self.games().contains(&game_id),
+            ERR_GAME_NOT_FOUND
+        );
+        require!(
+            payment_amount > 0,
+            ERR_PAYMENT_ZERO
+        );
+        self.pathfinder_tokens(game_id).insert(payment_token.clone());
+        self.pathfinder_pool(game_id, &payment_token).update(|val| *val += payment_amount);
         Ok(())
     }
 
@@ -139,11 +723,14 @@ pub trait SetupModule:
     #[allow(clippy::too_many_arguments)]
     fn init_position(
         &self,
+        game_id: u64,
         row: usize,
         col: usize,
     ) -> SCResult<()> {
-        self.player_position().set(Position{row: row, col: col});
-        self.start_position().set(Position{row: row, col: col});
+        self.require_in_maze(game_id, row, col);
+        self.player_position(game_id).set(Position{row, col});
+        self.start_position(game_id).set(Position{row, col});
+        self.visited_cells(game_id).insert(Position{row, col});
         Ok(())
     }
 
@@ -159,6 +746,152 @@ pub trait SetupModule:
         Ok(())
     }
 
+    // Function used to set the mode used to pick the winning move of a round.
+    #[only_owner]
+    #[endpoint(setSelectionMode)]
+    #[allow(clippy::too_many_arguments)]
+    fn set_selection_mode(
+        &self,
+        mode: SelectionMode,
+    ) -> SCResult<()> {
+        self.selection_mode().set(mode);
+        Ok(())
+    }
+
+    // Function used to set the policy applied to the moves that aren't picked in a round.
+    #[only_owner]
+    #[endpoint(setLosingMovePolicy)]
+    #[allow(clippy::too_many_arguments)]
+    fn set_losing_move_policy(
+        &self,
+        policy: LosingMovePolicy,
+    ) -> SCResult<()> {
+        self.losing_move_policy().set(policy);
+        Ok(())
+    }
+
+    // Function used to set the percent of the payment sent back for a losing move with the Refund policy (MULTIPLIER_TOTAL = 100%).
+    #[only_owner]
+    #[endpoint(setRefundPercent)]
+    #[allow(clippy::too_many_arguments)]
+    fn set_refund_percent(
+        &self,
+        percent: BigUint,
+    ) -> SCResult<()> {
+        // More than the payment can't be sent back.
+        require!(
+            percent <= MULTIPLIER_TOTAL,
+            ERR_REFUND_PERCENT
+        );
+        self.refund_percent().set(percent);
+        Ok(())
+    }
+
+    // Function used to set the bounty in "token" paid to the caller of resolveRound.
+    #[only_owner]
+    #[endpoint(setKeeperBounty)]
+    #[allow(clippy::too_many_arguments)]
+    fn set_keeper_bounty(
+        &self,
+        token: EgldOrEsdtTokenIdentifier,
+        bounty: BigUint,
+    ) -> SCResult<()> {
+        self.keeper_bounty(&token).set(bounty);
+        Ok(())
+    }
+
+    // Function used to enable or disable the async clock made with the pong contract.
+    // When disabled, rounds are resolved through the resolveRound endpoint.
+    #[only_owner]
+    #[endpoint(setAsyncClock)]
+    #[allow(clippy::too_many_arguments)]
+    fn set_async_clock(
+        &self,
+        enabled: bool,
+    ) -> SCResult<()> {
+        self.async_clock().set(enabled);
+        Ok(())
+    }
+
+    // Function used to enable or disable the fog of war mode, hiding the blocks far from the visited blocks until the game ends.
+    #[only_owner]
+    #[endpoint(setFogOfWar)]
+    #[allow(clippy::too_many_arguments)]
+    fn set_fog_of_war(
+        &self,
+        enabled: bool,
+    ) -> SCResult<()> {
+        self.fog_of_war().set(enabled);
+        Ok(())
+    }
+
+    // Function used to set the number of rows and columns around a visited block that stay visible in fog of war mode.
+    #[only_owner]
+    #[endpoint(setFogRadius)]
+    #[allow(clippy::too_many_arguments)]
+    fn set_fog_radius(
+        &self,
+        radius: usize,
+    ) -> SCResult<()> {
+        self.fog_radius().set(radius);
+        Ok(())
+    }
+
+    // Function used to enable or disable the commit-reveal mode.
+    // The mode can only change while no round is ongoing in the open games.
+    #[only_owner]
+    #[endpoint(setCommitReveal)]
+    #[allow(clippy::too_many_arguments)]
+    fn set_commit_reveal(
+        &self,
+        enabled: bool,
+    ) -> SCResult<()> {
+        for game_id in self.games().iter() {
+            require!(
+                self.round_status(game_id).get() == RoundStatus::Idle,
+                ERR_ROUND_NOT_IDLE
+            );
+        }
+        self.commit_reveal().set(enabled);
+        Ok(())
+    }
+
+    // Function used to set the duration of the reveal window following the round duration in commit-reveal mode.
+    #[only_owner]
+    #[endpoint(setRevealDuration)]
+    #[allow(clippy::too_many_arguments)]
+    fn set_reveal_duration(
+        &self,
+        duration: u64,
+    ) -> SCResult<()> {
+        self.reveal_duration().set(duration);
+        Ok(())
+    }
+
+    // Function used to set the time left after the round end to reveal the blocks of a committed maze, before the round can be cancelled.
+    #[only_owner]
+    #[endpoint(setCellRevealTimeout)]
+    #[allow(clippy::too_many_arguments)]
+    fn set_cell_reveal_timeout(
+        &self,
+        timeout: u64,
+    ) -> SCResult<()> {
+        self.cell_reveal_timeout().set(timeout);
+        Ok(())
+    }
+
+    // Function used to set the policy applied to the commits that aren't revealed.
+    #[only_owner]
+    #[endpoint(setUnrevealedPolicy)]
+    #[allow(clippy::too_many_arguments)]
+    fn set_unrevealed_policy(
+        &self,
+        policy: UnrevealedPolicy,
+    ) -> SCResult<()> {
+        self.unrevealed_policy().set(policy);
+        Ok(())
+    }
+
     // Function used to only set the starting position.
     // This is the position to which the player gets reset when walking into a trap.
     #[only_owner]
@@ -166,10 +899,12 @@ pub trait SetupModule:
     #[allow(clippy::too_many_arguments)]
     fn set_start_position(
         &self,
+        game_id: u64,
         row: usize,
         col: usize,
     ) -> SCResult<()> {
-        self.start_position().set(Position{row: row, col: col});
+        self.require_in_maze(game_id, row, col);
+        self.start_position(game_id).set(Position{row, col});
         Ok(())
     }
 
@@ -179,10 +914,13 @@ pub trait SetupModule:
     #[allow(clippy::too_many_arguments)]
     fn set_player_position(
         &self,
+        game_id: u64,
         row: usize,
         col: usize,
     ) -> SCResult<()> {
-        self.player_position().set(Position{row: row, col: col});
+        self.require_in_maze(game_id, row, col);
+        self.player_position(game_id).set(Position{row, col});
+        self.visited_cells(game_id).insert(Position{row, col});
         Ok(())
     }
 
@@ -203,9 +941,10 @@ pub trait SetupModule:
     #[allow(clippy::too_many_arguments)]
     fn set_timer(
         &self,
+        game_id: u64,
         start_timer: u64,
     ) -> SCResult<()> {
-        self.timer().set(start_timer);
+        self.timer(game_id).set(start_timer);
         Ok(())
     }
 
@@ -218,12 +957,90 @@ pub trait SetupModule:
         self.status().set(status);
     }
 
-    // Set the can make move mapper.
-    // This mapper is used to know if the clock is active and users can register moves.
+    // Set the round status.
+    // This status is used to know if the clock is active and the round is collecting moves.
     #[only_owner]
-    #[endpoint(setCanMakeMove)]
-    fn set_can_make_move(&self)
+    #[endpoint(setRoundStatus)]
+    fn set_round_status(&self, game_id: u64, status: RoundStatus)
     {
-        self.can_make_move().set(false);
+        self.round_status(game_id).set(status);
+    }
+
+    // Function used to check the matrix once stored and open the game.
+    fn open_game(
+        &self,
+        game_id: u64,
+    ) {
+        self.validate_matrix(game_id);
+        self.game_status(game_id).set(GameStatus::Start);
+        self.round_status(game_id).set(RoundStatus::Idle);
+        self.visited_cells(game_id).insert(self.player_position(game_id).get());
+        self.game_start(game_id).set(self.blockchain().get_block_timestamp());
+        self.matrix_hash(game_id).set(self.get_matrix_hash(game_id));
+        self.matrix_set_event(game_id, &self.blockchain().get_caller(), &self.matrix_dimensions(game_id).get());
+    }
+
+    // Function used to check that the matrix of a game session can be edited: set, not committed, and the game not started or the contract paused.
+    fn require_matrix_editable(
+        &self,
+        game_id: u64,
+    ) {
+        require!(
+            !self.matrix_dimensions(game_id).is_empty(),
+            ERR_MATRIX_NOT_SET
+        );
+        require!(
+            self.matrix_root(game_id).is_empty(),
+            ERR_COMMITTED_MAZE
+        );
+        require!(
+            self.game_status(game_id).get() != GameStatus::Start || self.status().get() == Status::Frozen,
+            ERR_MATRIX_NOT_EDITABLE
+        );
+    }
+
+    // Function used to replace the blocks of a row.
+    fn set_row(
+        &self,
+        game_id: u64,
+        row: usize,
+        blocks: MultiValueEncoded<Block<Self::Api>>,
+    ) {
+        let mut matrix_row = self.matrix(game_id, row);
+        matrix_row.clear();
+        for block in blocks.into_iter() {
+            matrix_row.push(&block);
+        }
+    }
+
+    // Function used to check the matrix once edited and rebuild its indexes.
+    // The player keeps "player_position" in a started game if it is still a block of the maze, the start position otherwise.
+    fn finish_matrix_edit(
+        &self,
+        game_id: u64,
+        player_position: Position,
+    ) {
+        self.rebuild_indexes(game_id);
+        self.validate_matrix(game_id);
+        if self.game_status(game_id).get() == GameStatus::Start
+            && self.is_in_maze(&self.matrix_dimensions(game_id).get(), &player_position) {
+            self.player_position(game_id).set(player_position);
+        }
+        self.matrix_hash(game_id).set(self.get_matrix_hash(game_id));
+        self.matrix_set_event(game_id, &self.blockchain().get_caller(), &self.matrix_dimensions(game_id).get());
+    }
+
+    // Function used to check that a position set by the owner is a block of the maze.
+    fn require_in_maze(
+        &self,
+        game_id: u64,
+        row: usize,
+        col: usize,
+    ) {
+        require!(
+            !self.matrix_dimensions(game_id).is_empty()
+                && self.is_in_maze(&self.matrix_dimensions(game_id).get(), &Position{row, col}),
+            ERR_OUT_OF_BOUNDS
+        );
     }
 }
@@ -0,0 +1,134 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::errors::{
+    ERR_MATRIX_NOT_SET,
+    ERR_TOO_MANY_KEYS,
+};
+
+use crate::storage::{
+    Block,
+    Dimensions,
+    MazeSolution,
+    Move,
+    Position,
+    PrizeType,
+    SearchState,
+    VisitedState,
+};
+
+#[multiversx_sc::module]
+pub trait SolverModule:
+    crate::storage::StorageModule
+    + crate::movement::MovementModule
+{
+    // Function used to check that the finish of a game session can be reached from the start position.
+    // Breadth-first search over the matrix with the move rules of the players, every jump length being a round.
+    // Doors can only be crossed once their key has been picked up, random blocks are considered without effect.
+    #[view(getMazeSolution)]
+    fn get_maze_solution(&self, game_id: u64) -> MazeSolution {
+        require!(
+            !self.matrix_dimensions(game_id).is_empty(),
+            ERR_MATRIX_NOT_SET
+        );
+        let dimensions = self.matrix_dimensions(game_id).get();
+
+        // Each key of the maze is tracked as one bit of the collected keys mask.
+        let mut key_ids: ManagedVec<u64> = ManagedVec::new();
+        for key_id in self.maze_keys_id(game_id).iter() {
+            key_ids.push(key_id);
+        }
+        require!(
+            key_ids.len() <= 64,
+            ERR_TOO_MANY_KEYS
+        );
+
+        // Visited key masks of each block, as linked lists of indexes in "visited" (0 means none).
+        let mut cell_heads: ManagedVec<usize> = ManagedVec::new();
+        for _ in 0..dimensions.rows * dimensions.cols {
+            cell_heads.push(0);
+        }
+        let mut visited: ManagedVec<VisitedState> = ManagedVec::new();
+        let mut queue: ManagedVec<SearchState> = ManagedVec::new();
+
+        let start = self.start_position(game_id).get();
+        self.visit(&mut cell_heads, &mut visited, &dimensions, &start, 0);
+        queue.push(SearchState { row: start.row, col: start.col, keys: 0, rounds: 0 });
+
+        let mut head = 0;
+        while head < queue.len() {
+            let state = queue.get(head);
+            head += 1;
+            let position = Position { row: state.row, col: state.col };
+
+            for direction in [Move::Up, Move::Right, Move::Down, Move::Left] {
+                let mut jump = 1;
+                while self.find_obstacle(game_id, &position, jump, &direction, |door_id| state.keys & self.key_bit(&key_ids, door_id) != 0).is_none() {
+                    let mut landing = self.get_new_position(&position, &direction, jump);
+                    let mut keys = state.keys;
+                    match self.matrix(game_id, landing.row).get(landing.col) {
+                        Block::Prize(prize_info) => {
+                            if prize_info.prize_type == PrizeType::Finish {
+                                return MazeSolution { reachable: true, path_length: state.rounds + 1 };
+                            }
+                        },
+                        Block::Key(key_id) => keys |= self.key_bit(&key_ids, key_id),
+                        Block::Trap => landing = start.clone(),
+                        Block::Teleport(pair_id) => {
+                            for exit in self.teleports(game_id, pair_id).iter() {
+                                if exit != landing {
+                                    landing = exit;
+                                    break;
+                                }
+                            }
+                        },
+                        _ => (),
+                    }
+                    if self.visit(&mut cell_heads, &mut visited, &dimensions, &landing, keys) {
+                        queue.push(SearchState { row: landing.row, col: landing.col, keys, rounds: state.rounds + 1 });
+                    }
+                    jump += 1;
+                }
+            }
+        }
+
+        MazeSolution { reachable: false, path_length: 0 }
+    }
+
+    // Function used to mark a block as visited with the "keys" mask, returns false if it was already visited with the same keys or more.
+    fn visit(
+        &self,
+        cell_heads: &mut ManagedVec<usize>,
+        visited: &mut ManagedVec<VisitedState>,
+        dimensions: &Dimensions,
+        position: &Position,
+        keys: u64,
+    ) -> bool {
+        let cell = (position.row - 1) * dimensions.cols + position.col - 1;
+        let mut index = cell_heads.get(cell);
+        while index != 0 {
+            let state = visited.get(index - 1);
+            if state.keys & keys == keys {
+                return false;
+            }
+            index = state.next;
+        }
+        visited.push(VisitedState { keys, next: cell_heads.get(cell) });
+        let _ = cell_heads.set(cell, &visited.len());
+        true
+    }
+
+    // Function used to get the bit of "key_id" in the collected keys mask, 0 for a key that isn't in the maze.
+    fn key_bit(
+        &self,
+        key_ids: &ManagedVec<u64>,
+        key_id: u64,
+    ) -> u64 {
+        for (index, id) in key_ids.iter().enumerate() {
+            if id == key_id {
+                return 1 << index;
+            }
+        }
+        0
+    }
+}
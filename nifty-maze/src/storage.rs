@@ -1,14 +1,51 @@
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
+use crate::MULTIPLIER_TOTAL;
+
 // Coordinates of the player.
-#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, PartialEq, NestedDecode, Clone)]
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, PartialEq, NestedDecode, Clone, ManagedVecItem)]
 pub struct Position
 {
     pub row: usize,
     pub col: usize,
 }
 
+// Dimensions of the maze matrix.
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, PartialEq, NestedDecode, Clone)]
+pub struct Dimensions
+{
+    pub rows: usize,
+    pub cols: usize,
+}
+
+// Result of the maze solvability check.
+// Path length is the minimum number of rounds needed to reach the finish.
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, PartialEq, NestedDecode, Clone)]
+pub struct MazeSolution
+{
+    pub reachable: bool,
+    pub path_length: u64,
+}
+
+// State of the maze solver: a position, the collected keys as a bit mask and the number of rounds to reach it.
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, PartialEq, NestedDecode, Clone, ManagedVecItem)]
+pub struct SearchState
+{
+    pub row: usize,
+    pub col: usize,
+    pub keys: u64,
+    pub rounds: u64,
+}
+
+// Key mask already visited on a block by the maze solver, linked to the next visited key mask of the same block.
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, PartialEq, NestedDecode, Clone, ManagedVecItem)]
+pub struct VisitedState
+{
+    pub keys: u64,
+    pub next: usize,
+}
+
 // Information of a prize in the maze.
 #[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, PartialEq, NestedDecode, Clone)]
 pub struct PrizeInfo<M: ManagedTypeApi>
@@ -20,7 +57,7 @@ pub struct PrizeInfo<M: ManagedTypeApi>
 }
 
 // Information of a move registered by a player.
-#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, PartialEq, NestedDecode, Clone)]
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, PartialEq, NestedDecode, Clone, ManagedVecItem)]
 pub struct PlayerMove<M: ManagedTypeApi>
 {
     pub payment_token: EgldOrEsdtTokenIdentifier<M>,
@@ -47,15 +84,16 @@ pub enum Block<M: ManagedTypeApi>{
     Wall,
     Trap,
     Prize(PrizeInfo<M>),
-    Random,
-    Teleport,
+    Random(u64), // Outcome table id.
+    Teleport(u64), // Pair id, a teleport leads to the other teleport sharing the same id.
     Key(u64),
     Door(u64),
     MatrixDimensions(usize, usize), // Put at the end of the matrix
+    Unknown, // Returned by the views for the blocks hidden by the fog of war.
 }
 
 // Possible directions to move.
-#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Clone)]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Clone, ManagedVecItem)]
 pub enum Move {
     None,
     Up,
@@ -64,6 +102,30 @@ pub enum Move {
     Left
 }
 
+// Outcomes that can be rolled when landing on a random block.
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Clone)]
+pub enum RandomOutcome {
+    None,
+    BackToStart,
+    RandomMove,
+    RandomKey,
+    Bonus,
+}
+
+// Weights of each outcome of a random block.
+// An outcome is rolled with a chance of its weight divided by the sum of all weights.
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, PartialEq, NestedDecode, Clone)]
+pub struct RandomTable<M: ManagedTypeApi>
+{
+    pub back_to_start: u64,
+    pub random_move: u64,
+    pub random_key: u64,
+    pub bonus: u64,
+    pub move_jump: usize, // Number of cells moved by the random move outcome.
+    pub bonus_token: EgldOrEsdtTokenIdentifier<M>, // Payment token of the bonus pool paying the bonus outcome.
+    pub bonus_amount: BigUint<M>, // Amount of payment tokens paid from the bonus pool by the bonus outcome.
+}
+
 // Status of the ongoing game.
 #[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Clone)]
 pub enum GameStatus {
@@ -72,8 +134,61 @@ pub enum GameStatus {
     End,
 }
 
-// Information of the payment token used to play by users.
+// Total value of the payment tokens, converted with the token weights, registered on a move option (direction and jump) during a round.
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, PartialEq, NestedDecode, Clone, ManagedVecItem)]
+pub struct MoveTotal<M: ManagedTypeApi>
+{
+    pub player_move: Move,
+    pub jump: usize,
+    pub amount: BigUint<M>
+}
+
+// Mode used to pick the winning move of a round.
+// Uniform: every move has the same chance, StakeWeighted: chance proportional to the payment amount, MostFunded: move option with the most payment tokens.
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Clone)]
+pub enum SelectionMode {
+    Uniform,
+    StakeWeighted,
+    MostFunded,
+}
+
+// Policy applied to the moves that aren't picked in a round.
+// Keep: payment stays collected, Refund: a percent of the payment is sent back, Credit: payment is credited toward the next moves of the player.
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Clone)]
+pub enum LosingMovePolicy {
+    Keep,
+    Refund,
+    Credit,
+}
+
+// Move committed by a player in commit-reveal mode, the hash hiding the move until it is revealed.
 #[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, PartialEq, NestedDecode, Clone)]
+pub struct MoveCommit<M: ManagedTypeApi>
+{
+    pub hash: ManagedByteArray<M, 32>,
+    pub payment_token: EgldOrEsdtTokenIdentifier<M>,
+    pub payment_amount: BigUint<M>,
+}
+
+// Policy applied to the commits that aren't revealed before the end of the reveal window.
+// Forfeit: payment stays collected, Refund: payment is sent back.
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Clone)]
+pub enum UnrevealedPolicy {
+    Forfeit,
+    Refund,
+}
+
+// Status of the ongoing round of moves.
+// Idle until the first move of a round, Collecting until the round duration is over and Resolving while the winning move is applied.
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Clone)]
+pub enum RoundStatus {
+    Idle,
+    Collecting,
+    Resolving,
+}
+
+// Information of the payment token used to play by users.
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, PartialEq, NestedDecode, Clone, ManagedVecItem)]
 pub struct PaymentToken<M: ManagedTypeApi>
 {
     pub ticker: EgldOrEsdtTokenIdentifier<M>,
@@ -81,10 +196,29 @@ pub struct PaymentToken<M: ManagedTypeApi>
     pub amount: BigUint<M>
 }
 
+// Split of the payment tokens collected during a game session, each share being a percent (MULTIPLIER_TOTAL = 100%).
+// The shares must sum to 100%.
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, PartialEq, NestedDecode, Clone)]
+pub struct RevenueSplit<M: ManagedTypeApi>
+{
+    pub leaderboard: BigUint<M>, // Share of the top spenders, split along the rank curve.
+    pub treasury: BigUint<M>,
+    pub jackpot: BigUint<M>, // Share rolling over into the jackpot of the next game session.
+    pub royalty: BigUint<M>, // Share of the creator or level designer of the maze.
+}
+
+// Shares of the revenue split paid through their own endpoints.
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Clone)]
+pub enum RevenueShare {
+    Treasury,
+    Jackpot,
+    Royalty,
+}
+
 // Information of the special prizes to win.
 // This information is stored in a storage mapper and is used to track all winners without altering the maze.
 // Winner is set to ManagedAddress::zero() until a winner for the prize is selected.
-#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, PartialEq, NestedDecode, Clone)]
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, PartialEq, NestedDecode, Clone, ManagedVecItem)]
 pub struct PrizeToWin<M: ManagedTypeApi>
 {
     pub ticker: EgldOrEsdtTokenIdentifier<M>,
@@ -93,14 +227,66 @@ pub struct PrizeToWin<M: ManagedTypeApi>
     pub winner: ManagedAddress<M>
 }
 
-// Structure used only to get the current mvp information through the get_mvp query
-#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, PartialEq, NestedDecode, Clone)]
+// Structure used to get the ranked players through the get_leaderboard and get_mvp queries.
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, PartialEq, NestedDecode, Clone, ManagedVecItem)]
 pub struct PlayerPayment<M: ManagedTypeApi>
 {
     pub address: ManagedAddress<M>,
     pub amount: BigUint<M>
 }
 
+// Record of a resolved round, the winning move being empty (zero address) for a round without move to pick.
+// Total paid is the value of the moves of the round, converted with the token weights.
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, PartialEq, NestedDecode, Clone)]
+pub struct RoundRecord<M: ManagedTypeApi>
+{
+    pub round: u64,
+    pub position_before: Position,
+    pub position_after: Position,
+    pub winning_move: PlayerMove<M>,
+    pub moves_count: usize,
+    pub total_paid: BigUint<M>,
+    pub block: Block<M>, // Block the winning move lands on.
+}
+
+// Summary of a completed game session, archived when the game ends.
+// The matrix hash is the keccak256 of the blocks set, row by row.
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, PartialEq, NestedDecode, Clone)]
+pub struct GameSummary<M: ManagedTypeApi>
+{
+    pub game_id: u64,
+    pub start_timestamp: u64,
+    pub end_timestamp: u64,
+    pub matrix_hash: ManagedByteArray<M, 32>,
+    pub rounds: u64,
+    pub mvp: PlayerPayment<M>,
+    pub prize_winners: ManagedVec<M, PrizeToWin<M>>,
+    pub collected: ManagedVec<M, PaymentToken<M>>,
+    pub collected_value: BigUint<M>,
+}
+
+// Statistics of a player during a game session, returned by the get_player_stats query.
+// Rank is the position of the player in the leaderboard, 0 for an address that hasn't played.
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, PartialEq, NestedDecode, Clone)]
+pub struct PlayerStats<M: ManagedTypeApi>
+{
+    pub total_paid: BigUint<M>,
+    pub moves_submitted: u64,
+    pub moves_selected: u64,
+    pub cells_advanced: u64,
+    pub traps_triggered: u64,
+    pub keys_collected: u64,
+    pub prizes_collected: u64,
+    pub rank: usize,
+}
+
+// Area shown by the views in fog of war mode, loaded once per query: the blocks at most "radius" rows and columns away from a visited block.
+pub struct VisibleArea<M: ManagedTypeApi>
+{
+    pub radius: usize,
+    pub visited_cells: ManagedVec<M, Position>,
+}
+
 // Return types used to get all important information froma single query to the contract.
 #[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Clone)]
 pub enum ReturnTypes<M: ManagedTypeApi> {
@@ -117,6 +303,9 @@ pub enum ReturnTypes<M: ManagedTypeApi> {
     TypeKey(u64),
     TypePrize(PrizeToWin<M>),
     TypePlayerMove(PlayerMove<M>),
+    TypeRoundStatus(RoundStatus),
+    TypeMoveTotal(MoveTotal<M>),
+    TypeCollectedToken(PaymentToken<M>),
 }
 
 // Status of the contract.
@@ -135,165 +324,727 @@ pub trait StorageModule {
     #[storage_mapper("rewardTokens")]
     fn reward_tokens(&self, prize_type: Block<Self::Api>) -> SingleValueMapper<PrizeInfo<Self::Api>>;
 
-    // Payment Token Ticker
-    #[view(getPaymentToken)]
-    #[storage_mapper("paymentToken")]
-    fn payment_token(&self) -> SingleValueMapper<EgldOrEsdtTokenIdentifier>;
+    // Tokens accepted as payment for the moves.
+    #[view(getPaymentTokens)]
+    #[storage_mapper("paymentTokens")]
+    fn payment_tokens(&self) -> UnorderedSetMapper<EgldOrEsdtTokenIdentifier>;
 
-    // Payment Token Quantity
-    #[view(getPaymentAmount)]
-    #[storage_mapper("paymentAmount")]
-    fn payment_amount(&self) -> SingleValueMapper<BigUint>;
+    // Price of a jump paid with "token".
+    #[view(getTokenPrice)]
+    #[storage_mapper("tokenPrice")]
+    fn token_price(&self, token: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    // Conversion weight of "token" used to compare payments made with different tokens.
+    // An amount of "token" is worth amount * weight / MULTIPLIER_TOTAL.
+    #[view(getTokenWeight)]
+    #[storage_mapper("tokenWeight")]
+    fn token_weight(&self, token: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    // Value of "amount" of "token" once converted with the token weight.
+    fn get_weighted_value(&self, token: &EgldOrEsdtTokenIdentifier, amount: &BigUint) -> BigUint {
+        amount * &self.token_weight(token).get() / MULTIPLIER_TOTAL
+    }
+
+    // Id of the last game session created.
+    #[view(getLastGameId)]
+    #[storage_mapper("lastGameId")]
+    fn last_game_id(&self) -> SingleValueMapper<u64>;
+
+    // Ids of the game sessions that are open.
+    // All game session mappers below are namespaced by the game id.
+    #[view(getGames)]
+    #[storage_mapper("games")]
+    fn games(&self) -> UnorderedSetMapper<u64>;
 
     // Player position
     #[view(getPlayerPosition)]
     #[storage_mapper("playerPosition")]
-    fn player_position(&self) -> SingleValueMapper<Position>;
+    fn player_position(&self, game_id: u64) -> SingleValueMapper<Position>;
 
     // Start position
     #[view(getStartPosition)]
     #[storage_mapper("startPosition")]
-    fn start_position(&self) -> SingleValueMapper<Position>;
+    fn start_position(&self, game_id: u64) -> SingleValueMapper<Position>;
 
     // Matrix: Row starts at 1
-    #[view(getMatrix)]
     #[storage_mapper("matrix")]
-    fn matrix(&self, row: usize) -> VecMapper<Block<Self::Api>>;
+    fn matrix(&self, game_id: u64, row: usize) -> VecMapper<Block<Self::Api>>;
+
+    // Blocks of a row of the matrix, the blocks hidden by the fog of war being unknown.
+    #[view(getMatrix)]
+    fn get_matrix(&self, game_id: u64, row: usize) -> MultiValueEncoded<Block<Self::Api>> {
+        let mut blocks: MultiValueEncoded<Block<Self::Api>> = MultiValueEncoded::new();
+        let visible_area = self.get_visible_area(game_id);
+        for (index, block) in self.matrix(game_id, row).iter().enumerate() {
+            blocks.push(self.get_visible_block(&visible_area, row, index + 1, block));
+        }
+        blocks
+    }
+
+    // Dimensions of the matrix, set once the matrix is validated.
+    #[view(getMatrixDimensions)]
+    #[storage_mapper("matrixDimensions")]
+    fn matrix_dimensions(&self, game_id: u64) -> SingleValueMapper<Dimensions>;
 
     // Matrix: Row starts at 1
     #[view(getFullMatrix)]
-    fn get_full_matrix(&self) -> MultiValueEncoded<Block<Self::Api>> {
+    fn get_full_matrix(&self, game_id: u64) -> MultiValueEncoded<Block<Self::Api>> {
         let mut matrix: MultiValueEncoded<Block<Self::Api>> = MultiValueEncoded::new(); 
+        let visible_area = self.get_visible_area(game_id);
         let mut row = 1;
-        while !self.matrix(row).is_empty() {
-            for block in self.matrix(row).iter() {
-                matrix.push(block);
+        while !self.matrix(game_id, row).is_empty() {
+            for (index, block) in self.matrix(game_id, row).iter().enumerate() {
+                matrix.push(self.get_visible_block(&visible_area, row, index + 1, block));
             }
             row += 1;
         }
         // We add the matrix dimensions at the end to give more information about the shape of the maze.
-        matrix.push(Block::MatrixDimensions(row-1, self.matrix(row-1).len()));
-        return matrix;
+        matrix.push(Block::MatrixDimensions(row-1, self.matrix(game_id, row-1).len()));
+        matrix
+    }
+
+    // Blocks of "count" rows of the matrix from "from_row", row by row, without the matrix dimensions sentinel.
+    // Rows start at 1, row 0 being read as row 1.
+    #[view(getMatrixRows)]
+    fn get_matrix_rows(&self, game_id: u64, from_row: usize, count: usize) -> MultiValueEncoded<Block<Self::Api>> {
+        let mut blocks: MultiValueEncoded<Block<Self::Api>> = MultiValueEncoded::new();
+        let rows = self.matrix_dimensions(game_id).get().rows;
+        let visible_area = self.get_visible_area(game_id);
+        let first_row = core::cmp::max(from_row, 1);
+        let mut row = first_row;
+        while row <= rows && row < first_row.saturating_add(count) {
+            for (index, block) in self.matrix(game_id, row).iter().enumerate() {
+                blocks.push(self.get_visible_block(&visible_area, row, index + 1, block));
+            }
+            row += 1;
+        }
+        blocks
+    }
+
+    // Blocks at most "radius" rows and columns away from "position", with their row and column, the maze edge cutting the area.
+    #[view(getBlocksAround)]
+    fn get_blocks_around(&self, game_id: u64, position: Position, radius: usize) -> MultiValueEncoded<MultiValue3<usize, usize, Block<Self::Api>>> {
+        let mut blocks: MultiValueEncoded<MultiValue3<usize, usize, Block<Self::Api>>> = MultiValueEncoded::new();
+        let dimensions = self.matrix_dimensions(game_id).get();
+        let last_row = core::cmp::min(position.row.saturating_add(radius), dimensions.rows);
+        let last_col = core::cmp::min(position.col.saturating_add(radius), dimensions.cols);
+        let visible_area = self.get_visible_area(game_id);
+        for row in core::cmp::max(position.row.saturating_sub(radius), 1)..=last_row {
+            let matrix_row = self.matrix(game_id, row);
+            for col in core::cmp::max(position.col.saturating_sub(radius), 1)..=last_col {
+                blocks.push((row, col, self.get_visible_block(&visible_area, row, col, matrix_row.get(col))).into());
+            }
+        }
+        blocks
     }
 
+    // Fog of war mode, hiding from the views the blocks far from the blocks visited by the player until the game ends.
+    #[view(getFogOfWar)]
+    #[storage_mapper("fogOfWar")]
+    fn fog_of_war(&self) -> SingleValueMapper<bool>;
+
+    // Number of rows and columns around a visited block that stay visible in fog of war mode.
+    #[view(getFogRadius)]
+    #[storage_mapper("fogRadius")]
+    fn fog_radius(&self) -> SingleValueMapper<usize>;
+
+    // Blocks the player has been on during the game session.
+    #[view(getVisitedCells)]
+    #[storage_mapper("visitedCells")]
+    fn visited_cells(&self, game_id: u64) -> UnorderedSetMapper<Position>;
+
+    // Function used to load the area shown by the views, None when the whole layout is shown.
+    // The whole layout is revealed once the game session has ended.
+    fn get_visible_area(&self, game_id: u64) -> Option<VisibleArea<Self::Api>> {
+        if !self.fog_of_war().get() || self.game_status(game_id).get() == GameStatus::End {
+            return None;
+        }
+        let mut visited_cells: ManagedVec<Position> = ManagedVec::new();
+        for position in self.visited_cells(game_id).iter() {
+            visited_cells.push(position);
+        }
+        Some(VisibleArea {
+            radius: self.fog_radius().get(),
+            visited_cells,
+        })
+    }
+
+    // Function used to get the block at "row" and "col" as shown by the views, unknown if outside the visible area.
+    fn get_visible_block(&self, visible_area: &Option<VisibleArea<Self::Api>>, row: usize, col: usize, block: Block<Self::Api>) -> Block<Self::Api> {
+        let area = match visible_area {
+            Some(area) => area,
+            None => return block,
+        };
+        for visited in area.visited_cells.iter() {
+            if visited.row.abs_diff(row) <= area.radius && visited.col.abs_diff(col) <= area.radius {
+                return block;
+            }
+        }
+        Block::Unknown
+    }
+
+    // Positions of the teleports sharing the same pair id.
+    #[storage_mapper("teleports")]
+    fn teleports(&self, game_id: u64, pair_id: u64) -> UnorderedSetMapper<Position>;
+
+    // Positions of the teleports sharing the same pair id, without the teleports hidden by the fog of war.
+    #[view(getTeleports)]
+    fn get_teleports(&self, game_id: u64, pair_id: u64) -> MultiValueEncoded<Position> {
+        let mut positions: MultiValueEncoded<Position> = MultiValueEncoded::new();
+        let visible_area = self.get_visible_area(game_id);
+        for position in self.teleports(game_id, pair_id).iter() {
+            if self.get_visible_block(&visible_area, position.row, position.col, Block::Teleport(pair_id)) != Block::Unknown {
+                positions.push(position);
+            }
+        }
+        positions
+    }
+
+    // Pair ids of all the teleports in the maze.
+    #[view(getTeleportIds)]
+    #[storage_mapper("teleportIds")]
+    fn teleport_ids(&self, game_id: u64) -> UnorderedSetMapper<u64>;
+
+    // Ids of all the keys in the maze.
+    #[view(getMazeKeys)]
+    #[storage_mapper("mazeKeysId")]
+    fn maze_keys_id(&self, game_id: u64) -> UnorderedSetMapper<u64>;
+
+    // Outcome table used by the random blocks sharing the same table id.
+    #[view(getRandomTable)]
+    #[storage_mapper("randomTable")]
+    fn random_table(&self, table_id: u64) -> SingleValueMapper<RandomTable<Self::Api>>;
+
+    // Amount of payment tokens available to pay the random blocks bonuses.
+    #[view(getBonusPool)]
+    #[storage_mapper("bonusPool")]
+    fn bonus_pool(&self, token: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<BigUint>;
+
     // Matrix: Row starts at 1
     #[view(getGameStatus)]
     #[storage_mapper("gameStatus")]
-    fn game_status(&self) -> SingleValueMapper<GameStatus>;
+    fn game_status(&self, game_id: u64) -> SingleValueMapper<GameStatus>;
 
     // Collected keys
     #[view(getKeys)]
     #[storage_mapper("collectedKeysId")]
-    fn collected_keys_id(&self) -> UnorderedSetMapper<u64>;
+    fn collected_keys_id(&self, game_id: u64) -> UnorderedSetMapper<u64>;
     
     // Amount of payment tokens collected.
     #[view(getCollectedTokens)]
     #[storage_mapper("collectedTokens")]
-    fn collected_tokens(&self) -> SingleValueMapper<BigUint>;
+    fn collected_tokens(&self, game_id: u64, token: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<BigUint>;
 
-    // Total amount paid by a player during this game session.
+    // Amount of payment tokens collected when the game session ended, the revenue shares being computed from it.
+    #[view(getFinalCollectedTokens)]
+    #[storage_mapper("finalCollectedTokens")]
+    fn final_collected_tokens(&self, game_id: u64, token: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    // Function used to freeze the amount of each payment token collected once the game session has ended.
+    fn snapshot_collected_tokens(&self, game_id: u64) {
+        for token in self.game_tokens(game_id).iter() {
+            self.final_collected_tokens(game_id, &token).set(self.collected_tokens(game_id, &token).get());
+        }
+    }
+
+    // Payment tokens paid during this game session.
+    #[view(getGameTokens)]
+    #[storage_mapper("gameTokens")]
+    fn game_tokens(&self, game_id: u64) -> UnorderedSetMapper<EgldOrEsdtTokenIdentifier>;
+
+    // Value of all the payment tokens collected during this game session.
+    #[view(getCollectedValue)]
+    fn get_collected_value(&self, game_id: u64) -> BigUint {
+        let mut value = BigUint::zero();
+        for token in self.game_tokens(game_id).iter() {
+            value += self.get_weighted_value(&token, &self.collected_tokens(game_id, &token).get());
+        }
+        value
+    }
+
+    // Total amount paid by a player with "token" during this game session.
     #[view(getPlayerPayments)]
     #[storage_mapper("playerPayments")]
-    fn player_payments(&self, address: &ManagedAddress) -> SingleValueMapper<BigUint>;
+    fn player_payments(&self, game_id: u64, address: &ManagedAddress, token: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    // Value of all the payments of a player during this game session.
+    #[view(getPlayerValue)]
+    fn get_player_value(&self, game_id: u64, address: &ManagedAddress) -> BigUint {
+        let mut value = BigUint::zero();
+        for token in self.game_tokens(game_id).iter() {
+            value += self.get_weighted_value(&token, &self.player_payments(game_id, address, &token).get());
+        }
+        value
+    }
+
+    // Split of the payment tokens collected, applied to the game sessions created afterwards.
+    #[view(getRevenueSplit)]
+    #[storage_mapper("revenueSplit")]
+    fn revenue_split(&self) -> SingleValueMapper<RevenueSplit<Self::Api>>;
+
+    // Split of the payment tokens collected during this game session, set when the game is created.
+    #[view(getGameRevenueSplit)]
+    #[storage_mapper("gameRevenueSplit")]
+    fn game_revenue_split(&self, game_id: u64) -> SingleValueMapper<RevenueSplit<Self::Api>>;
+
+    // Address receiving the treasury share.
+    #[view(getTreasuryAddress)]
+    #[storage_mapper("treasuryAddress")]
+    fn treasury_address(&self) -> SingleValueMapper<ManagedAddress>;
+
+    // Address of the creator or level designer of the maze receiving the royalty share.
+    #[view(getRoyaltyAddress)]
+    #[storage_mapper("royaltyAddress")]
+    fn royalty_address(&self, game_id: u64) -> SingleValueMapper<ManagedAddress>;
+
+    // Flag to know if a share of the revenue split has been paid.
+    #[view(getShareDistributed)]
+    #[storage_mapper("shareDistributed")]
+    fn share_distributed(&self, game_id: u64, share: &RevenueShare) -> SingleValueMapper<bool>;
+
+    // Jackpot rolling over into the next game session created.
+    #[view(getJackpot)]
+    #[storage_mapper("jackpot")]
+    fn jackpot(&self) -> SingleValueMapper<ManagedVec<PaymentToken<Self::Api>>>;
+
+    // Jackpot of this game session, paid to the player reaching the finish.
+    #[view(getGameJackpot)]
+    #[storage_mapper("gameJackpot")]
+    fn game_jackpot(&self, game_id: u64) -> SingleValueMapper<ManagedVec<PaymentToken<Self::Api>>>;
+
+    // Share of "token" collected during this game session for "percent", from the amount collected when the game session ended.
+    fn get_share_amount(&self, game_id: u64, token: &EgldOrEsdtTokenIdentifier, percent: &BigUint) -> BigUint {
+        self.final_collected_tokens(game_id, token).get() * percent / MULTIPLIER_TOTAL
+    }
 
-    // Percent applied to the MVP rewards.
-    #[view(getMvpPercent)]
-    #[storage_mapper("mvpPercent")]
-    fn mvp_percent(&self) -> SingleValueMapper<BigUint>;
+    // Timestamp of the matrix being set, starting the game session.
+    #[view(getGameStart)]
+    #[storage_mapper("gameStart")]
+    fn game_start(&self, game_id: u64) -> SingleValueMapper<u64>;
+
+    // Keccak256 hash of the matrix blocks when the matrix was set.
+    #[view(getMatrixHash)]
+    #[storage_mapper("matrixHash")]
+    fn matrix_hash(&self, game_id: u64) -> SingleValueMapper<ManagedByteArray<Self::Api, 32>>;
+
+    // Number of rounds resolved during the game session.
+    #[view(getRoundsPlayed)]
+    #[storage_mapper("roundsPlayed")]
+    fn rounds_played(&self, game_id: u64) -> SingleValueMapper<u64>;
+
+    // Records of the rounds resolved during the game session, the record of round N at index N.
+    #[storage_mapper("roundRecords")]
+    fn round_records(&self, game_id: u64) -> VecMapper<RoundRecord<Self::Api>>;
+
+    // Dimensions of the maze being uploaded in several transactions, empty once finalized.
+    #[view(getMazeUpload)]
+    #[storage_mapper("mazeUpload")]
+    fn maze_upload(&self, game_id: u64) -> SingleValueMapper<Dimensions>;
+
+    // Merkle root of the blocks of a maze committed through setMatrixCommitment, empty for a maze set through setMatrix.
+    #[view(getMatrixRoot)]
+    #[storage_mapper("matrixRoot")]
+    fn matrix_root(&self, game_id: u64) -> SingleValueMapper<ManagedByteArray<Self::Api, 32>>;
+
+    // Time left after the round end to reveal the blocks of a committed maze, before the round can be cancelled and its moves refunded.
+    #[view(getCellRevealTimeout)]
+    #[storage_mapper("cellRevealTimeout")]
+    fn cell_reveal_timeout(&self) -> SingleValueMapper<u64>;
+
+    // Winning move of a committed maze round, picked once the round is over and applied once the blocks on its path are revealed.
+    #[storage_mapper("pendingWinningMove")]
+    fn pending_winning_move(&self, game_id: u64) -> SingleValueMapper<PlayerMove<Self::Api>>;
+
+    // Summaries of the completed game sessions, in the order they ended.
+    // A game session replayed after its matrix has been cleared gets a new summary when it ends again.
+    #[storage_mapper("gameHistory")]
+    fn game_history(&self) -> VecMapper<GameSummary<Self::Api>>;
+
+    // Index in the game history of the latest summary of a game session.
+    #[storage_mapper("gameSummaryIndex")]
+    fn game_summary_index(&self, game_id: u64) -> SingleValueMapper<usize>;
 
     // Players mapper is used to get all players that have played at least once during the whole game session.
     #[view(getPlayers)]
     #[storage_mapper("players")]
-    fn players(&self) -> UnorderedSetMapper<ManagedAddress>;
+    fn players(&self, game_id: u64) -> UnorderedSetMapper<ManagedAddress>;
 
-    // Get information about the payment token used to play the game.
+    // Get information about the payment tokens used to play the game, with the price of a jump.
     #[view(getPaymentInfo)]
-    fn get_payment_info(&self) -> PaymentToken<Self::Api> {
-        PaymentToken{
-            ticker: self.payment_token().get(),
-            nonce: 0,
-            amount: self.payment_amount().get() 
+    fn get_payment_info(&self) -> ManagedVec<PaymentToken<Self::Api>> {
+        let mut payment_info: ManagedVec<PaymentToken<Self::Api>> = ManagedVec::new();
+        for token in self.payment_tokens().iter() {
+            payment_info.push(PaymentToken{
+                amount: self.token_price(&token).get(),
+                ticker: token,
+                nonce: 0,
+            });
+        }
+        payment_info
+    }
+
+    // Number of moves of the player picked as winning move of a round during the game session.
+    #[view(getPickedMoves)]
+    #[storage_mapper("pickedMoves")]
+    fn picked_moves(&self, game_id: u64, address: &ManagedAddress) -> SingleValueMapper<u64>;
+
+    // Number of moves picked during the game session, used to split the pathfinder pool.
+    #[view(getTotalPickedMoves)]
+    #[storage_mapper("totalPickedMoves")]
+    fn total_picked_moves(&self, game_id: u64) -> SingleValueMapper<u64>;
+
+    // Number of blocks the player moved forward with the picked moves, a blocked move advancing 0 blocks.
+    #[view(getCellsAdvanced)]
+    #[storage_mapper("cellsAdvanced")]
+    fn cells_advanced(&self, game_id: u64, address: &ManagedAddress) -> SingleValueMapper<u64>;
+
+    // Number of moves registered or committed by the player during the game session, replaced moves included.
+    #[view(getMovesSubmitted)]
+    #[storage_mapper("movesSubmitted")]
+    fn moves_submitted(&self, game_id: u64, address: &ManagedAddress) -> SingleValueMapper<u64>;
+
+    // Number of traps triggered by the picked moves of the player.
+    #[view(getTrapsTriggered)]
+    #[storage_mapper("trapsTriggered")]
+    fn traps_triggered(&self, game_id: u64, address: &ManagedAddress) -> SingleValueMapper<u64>;
+
+    // Number of keys collected by the picked moves of the player, random keys included.
+    #[view(getKeysCollected)]
+    #[storage_mapper("keysCollected")]
+    fn keys_collected(&self, game_id: u64, address: &ManagedAddress) -> SingleValueMapper<u64>;
+
+    // Number of prizes won by the picked moves of the player, the finish included.
+    #[view(getPrizesCollected)]
+    #[storage_mapper("prizesCollected")]
+    fn prizes_collected(&self, game_id: u64, address: &ManagedAddress) -> SingleValueMapper<u64>;
+
+    // Get the statistics of a player during the game session.
+    #[view(getPlayerStats)]
+    fn get_player_stats(&self, game_id: u64, address: ManagedAddress) -> PlayerStats<Self::Api> {
+        PlayerStats {
+            total_paid: self.get_player_value(game_id, &address),
+            moves_submitted: self.moves_submitted(game_id, &address).get(),
+            moves_selected: self.picked_moves(game_id, &address).get(),
+            cells_advanced: self.cells_advanced(game_id, &address).get(),
+            traps_triggered: self.traps_triggered(game_id, &address).get(),
+            keys_collected: self.keys_collected(game_id, &address).get(),
+            prizes_collected: self.prizes_collected(game_id, &address).get(),
+            rank: self.get_player_rank(game_id, &address),
+        }
+    }
+
+    // Position of the player in the leaderboard, ranks starting at 1, 0 for an address that hasn't played or whose payments are worth nothing.
+    fn get_player_rank(&self, game_id: u64, address: &ManagedAddress) -> usize {
+        if !self.players(game_id).contains(address) {
+            return 0;
+        }
+        let amount = self.get_player_value(game_id, address);
+        if amount == 0 {
+            return 0;
+        }
+        let index = self.players(game_id).get_index(address);
+        let mut rank = 1;
+        // Players spending more, or as much but paying first, are ranked before.
+        for (player_index, player) in self.players(game_id).iter().enumerate() {
+            let payment = self.get_player_value(game_id, &player);
+            if payment > amount || (payment == amount && player_index + 1 < index) {
+                rank += 1;
+            }
+        }
+        rank
+    }
+
+    // Pool of "token" funded by the owner, paid at the end of the game session to the players whose moves got picked.
+    #[view(getPathfinderPool)]
+    #[storage_mapper("pathfinderPool")]
+    fn pathfinder_pool(&self, game_id: u64, token: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    // Tokens of the pathfinder pool of the game session.
+    #[view(getPathfinderTokens)]
+    #[storage_mapper("pathfinderTokens")]
+    fn pathfinder_tokens(&self, game_id: u64) -> UnorderedSetMapper<EgldOrEsdtTokenIdentifier>;
+
+    // Flag to know if the player claimed the pathfinder rewards.
+    #[view(getPathfinderClaimed)]
+    #[storage_mapper("pathfinderClaimed")]
+    fn pathfinder_claimed(&self, game_id: u64, address: &ManagedAddress) -> SingleValueMapper<bool>;
+
+    // Percents of the leaderboard share paid to each rank, from the first rank (MULTIPLIER_TOTAL = 100%).
+    #[view(getRankCurve)]
+    #[storage_mapper("rankCurve")]
+    fn rank_curve(&self) -> SingleValueMapper<ManagedVec<BigUint>>;
+
+    // Rank curve in place when the game session was created.
+    #[view(getGameRankCurve)]
+    #[storage_mapper("gameRankCurve")]
+    fn game_rank_curve(&self, game_id: u64) -> SingleValueMapper<ManagedVec<BigUint>>;
+
+    // Flag to know if the player at "rank" claimed the rewards, ranks starting at 1.
+    #[view(getRankClaimed)]
+    #[storage_mapper("rankClaimed")]
+    fn rank_claimed(&self, game_id: u64, rank: usize) -> SingleValueMapper<bool>;
+
+    // Percent of the payment tokens collected paid to "rank", 0 for a rank outside the rank curve.
+    fn get_rank_percent(&self, game_id: u64, rank: usize) -> BigUint {
+        let curve = self.game_rank_curve(game_id).get();
+        if rank == 0 || rank > curve.len() {
+            return BigUint::zero();
         }
+        self.game_revenue_split(game_id).get().leaderboard * &*curve.get(rank - 1) / MULTIPLIER_TOTAL
     }
 
-    // Flag to know if the MVP claimed the rewards.
-    #[view(getMvpClaimed)]
-    #[storage_mapper("mvpClaimed")]
-    fn mvp_claimed(&self) -> SingleValueMapper<bool>;
+    // Percent of the payment tokens collected paid to the ranks of the rank curve left without ranked player.
+    fn get_unfilled_ranks_percent(&self, game_id: u64) -> BigUint {
+        let mut percent = BigUint::zero();
+        let mut ranked_players = 0;
+        for player in self.players(game_id).iter() {
+            if self.get_player_value(game_id, &player) > 0 {
+                ranked_players += 1;
+            }
+        }
+        for rank in ranked_players + 1..=self.game_rank_curve(game_id).get().len() {
+            percent += self.get_rank_percent(game_id, rank);
+        }
+        percent
+    }
 
     // Information of the special prizes to win.
     // This information is stored in a storage mapper and is used to track all winners without altering the maze.
     // Winner is set to ManagedAddress::zero() until a winner for the prize is selected.
     #[view(getPrizesToWin)]
     #[storage_mapper("prizesToWin")]
-    fn prizes_to_win(&self) -> UnorderedSetMapper<PrizeToWin<Self::Api>>;
+    fn prizes_to_win(&self, game_id: u64) -> UnorderedSetMapper<PrizeToWin<Self::Api>>;
 
     // Contract Status
     #[view(getStatus)]
     #[storage_mapper("status")]
     fn status(&self) -> SingleValueMapper<Status>;
 
-    // Get the address that has spent the most payment ESDT this game session.
-    #[view(getMvp)]
-    fn get_mvp(&self) -> PlayerPayment<Self::Api> {
-        let mut address = ManagedAddress::zero();
-        let mut amount = BigUint::zero();
-        for player in self.players().iter() {
-            let payment = self.player_payments(&player).get();
-            if payment > amount {
-                amount = payment;
-                address = player
+    // Get the "limit" players that have spent the most payment tokens this game session, payments being converted with the token weights.
+    // Ties go to the player who paid first, the players being stored in the order of their first payment.
+    // Players whose payments are worth nothing, refunded or credited for instance, aren't ranked.
+    #[view(getLeaderboard)]
+    fn get_leaderboard(&self, game_id: u64, limit: usize) -> ManagedVec<PlayerPayment<Self::Api>> {
+        let mut leaderboard: ManagedVec<PlayerPayment<Self::Api>> = ManagedVec::new();
+        for player in self.players(game_id).iter() {
+            let amount = self.get_player_value(game_id, &player);
+            if amount == 0 {
+                continue;
+            }
+            // Rank of the player among the players that paid before.
+            let mut rank = leaderboard.len();
+            while rank > 0 && leaderboard.get(rank - 1).amount < amount {
+                rank -= 1;
+            }
+            if rank >= limit {
+                continue;
             }
+            let mut ranked: ManagedVec<PlayerPayment<Self::Api>> = ManagedVec::new();
+            for (index, player_payment) in leaderboard.iter().enumerate() {
+                if index == rank {
+                    ranked.push(PlayerPayment {
+                        address: player.clone(),
+                        amount: amount.clone(),
+                    });
+                }
+                if ranked.len() < limit {
+                    ranked.push(player_payment);
+                }
+            }
+            if rank == leaderboard.len() {
+                ranked.push(PlayerPayment {
+                    address: player,
+                    amount,
+                });
+            }
+            leaderboard = ranked;
         }
-        PlayerPayment {
-            address: address,
-            amount: amount,
+        leaderboard
+    }
+
+    // Get the address that has spent the most payment tokens this game session, first of the leaderboard.
+    #[view(getMvp)]
+    fn get_mvp(&self, game_id: u64) -> PlayerPayment<Self::Api> {
+        let leaderboard = self.get_leaderboard(game_id, 1);
+        if leaderboard.is_empty() {
+            return PlayerPayment {
+                address: ManagedAddress::zero(),
+                amount: BigUint::zero(),
+            };
         }
+        leaderboard.get(0)
     }
 
     // View returning general data about the contract in a MultiValueEncoded structure.
     #[view(getGeneralData)]
-    fn get_general_data(&self) -> MultiValueEncoded<ReturnTypes<Self::Api>> {
+    fn get_general_data(&self, game_id: u64) -> MultiValueEncoded<ReturnTypes<Self::Api>> {
         let mut my_vec: MultiValueEncoded<ReturnTypes<Self::Api>> = MultiValueEncoded::new();
-        my_vec.push(ReturnTypes::TypePosition(self.player_position().get()));
-        my_vec.push(ReturnTypes::TypeGameStatus(self.game_status().get()));
+        my_vec.push(ReturnTypes::TypePosition(self.player_position(game_id).get()));
+        my_vec.push(ReturnTypes::TypeGameStatus(self.game_status(game_id).get()));
 
         my_vec.push(ReturnTypes::TypeCooldown(self.duration().get()));
-        my_vec.push(ReturnTypes::TypeTimerStart(self.timer().get())); // timestamp when clock starts.
-        my_vec.push(ReturnTypes::TypeCollectedTokens(self.collected_tokens().get()));
-        for key in self.collected_keys_id().iter() {
+        my_vec.push(ReturnTypes::TypeTimerStart(self.timer(game_id).get())); // timestamp when clock starts.
+        my_vec.push(ReturnTypes::TypeRoundStatus(self.round_status(game_id).get()));
+        my_vec.push(ReturnTypes::TypeCollectedTokens(self.get_collected_value(game_id)));
+        for token in self.game_tokens(game_id).iter() {
+            my_vec.push(ReturnTypes::TypeCollectedToken(PaymentToken {
+                amount: self.collected_tokens(game_id, &token).get(),
+                ticker: token,
+                nonce: 0,
+            }));
+        }
+        for key in self.collected_keys_id(game_id).iter() {
             my_vec.push(ReturnTypes::TypeKey(key));
         }
-        for prize in self.prizes_to_win().iter() {
+        for prize in self.prizes_to_win(game_id).iter() {
             my_vec.push(ReturnTypes::TypePrize(prize));
         }
-        for single_move in self.moves().iter() {
+        for single_move in self.get_moves(game_id).iter() {
             my_vec.push(ReturnTypes::TypePlayerMove(single_move));
         }
-        my_vec.push(ReturnTypes::TypeMvpClaimed(self.mvp_claimed().get()));
-        return my_vec;
+        for move_total in self.get_move_totals(game_id).iter() {
+            my_vec.push(ReturnTypes::TypeMoveTotal(move_total));
+        }
+        my_vec.push(ReturnTypes::TypeMvpClaimed(self.rank_claimed(game_id, 1).get()));
+        my_vec
     }    
 
 
     // Timer used to see how much time passed with our clock.
+    // Set to the timestamp of the first move of the round.
     #[view(getTimer)]
     #[storage_mapper("timer")]
-    fn timer(&self) -> SingleValueMapper<u64>;
+    fn timer(&self, game_id: u64) -> SingleValueMapper<u64>;
 
     // Duration of the clock (lower bound)
     #[view(getDuration)]
     #[storage_mapper("duration")]
     fn duration(&self) -> SingleValueMapper<u64>;
 
+    // Move registered by a player during a round, indexed by the player address.
+    #[view(getPlayerMove)]
+    #[storage_mapper("playerMove")]
+    fn player_move(&self, game_id: u64, address: &ManagedAddress) -> SingleValueMapper<PlayerMove<Self::Api>>;
+
     // All player registered moves during a round.
     #[view(getMoves)]
-    #[storage_mapper("moves")]
-    fn moves(&self) -> UnorderedSetMapper<PlayerMove<Self::Api>>;
+    fn get_moves(&self, game_id: u64) -> ManagedVec<PlayerMove<Self::Api>> {
+        let mut moves: ManagedVec<PlayerMove<Self::Api>> = ManagedVec::new();
+        for address in self.addresses_with_move(game_id).iter() {
+            moves.push(self.player_move(game_id, &address).get());
+        }
+        moves
+    }
+
+    // Move registered by the player at "index" (starting at 1) of the addresses with move.
+    fn get_move_by_index(&self, game_id: u64, index: usize) -> PlayerMove<Self::Api> {
+        self.player_move(game_id, &self.addresses_with_move(game_id).get_by_index(index)).get()
+    }
 
-    // Can make move flag (true when the clock starts).
-    #[view(getCanMakeMove)]
-    #[storage_mapper("canmakemove")]
-    fn can_make_move(&self) -> SingleValueMapper<bool>;
+    // Mode used to pick the winning move of a round.
+    #[view(getSelectionMode)]
+    #[storage_mapper("selectionMode")]
+    fn selection_mode(&self) -> SingleValueMapper<SelectionMode>;
+
+    // Policy applied to the moves that aren't picked in a round.
+    #[view(getLosingMovePolicy)]
+    #[storage_mapper("losingMovePolicy")]
+    fn losing_move_policy(&self) -> SingleValueMapper<LosingMovePolicy>;
+
+    // Percent of the payment sent back for a losing move with the Refund policy.
+    #[view(getRefundPercent)]
+    #[storage_mapper("refundPercent")]
+    fn refund_percent(&self) -> SingleValueMapper<BigUint>;
+
+    // Amount of payment tokens credited to a player from losing moves.
+    #[view(getPlayerCredit)]
+    #[storage_mapper("playerCredit")]
+    fn player_credit(&self, address: &ManagedAddress, token: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    // Number of jumps paid with "payment_amount" of "payment_token".
+    fn get_jump_size(&self, payment_token: &EgldOrEsdtTokenIdentifier, payment_amount: &BigUint) -> usize {
+        (payment_amount / &self.token_price(payment_token).get()).to_u64().unwrap() as usize
+    }
+
+    // Totals of payment tokens value registered on each move option (direction and jump) during this round.
+    #[view(getMoveTotals)]
+    fn get_move_totals(&self, game_id: u64) -> ManagedVec<MoveTotal<Self::Api>> {
+        let mut totals: ManagedVec<MoveTotal<Self::Api>> = ManagedVec::new();
+        for p_move in self.get_moves(game_id).iter() {
+            let jump = self.get_jump_size(&p_move.payment_token, &p_move.payment_amount);
+            let value = self.get_weighted_value(&p_move.payment_token, &p_move.payment_amount);
+            let mut found = false;
+            for (index, total) in totals.iter().enumerate() {
+                if total.player_move == p_move.player_move && total.jump == jump {
+                    let _ = totals.set(index, &MoveTotal {
+                        player_move: total.player_move,
+                        jump,
+                        amount: total.amount + &value,
+                    });
+                    found = true;
+                    break;
+                }
+            }
+            if !found {
+                totals.push(MoveTotal {
+                    player_move: p_move.player_move,
+                    jump,
+                    amount: value,
+                });
+            }
+        }
+        totals
+    }
+
+    // Timestamp from which the round can be resolved.
+    // In commit-reveal mode, the reveal window follows the round duration.
+    fn get_round_end(&self, game_id: u64) -> u64 {
+        let mut round_end = self.timer(game_id).get() + self.duration().get();
+        if self.commit_reveal().get() {
+            round_end += self.reveal_duration().get();
+        }
+        round_end
+    }
+
+    // Commit-reveal mode flag (true when moves are committed as hashes then revealed).
+    #[view(getCommitReveal)]
+    #[storage_mapper("commitReveal")]
+    fn commit_reveal(&self) -> SingleValueMapper<bool>;
+
+    // Duration of the reveal window following the round duration in commit-reveal mode.
+    #[view(getRevealDuration)]
+    #[storage_mapper("revealDuration")]
+    fn reveal_duration(&self) -> SingleValueMapper<u64>;
+
+    // Policy applied to the commits that aren't revealed.
+    #[view(getUnrevealedPolicy)]
+    #[storage_mapper("unrevealedPolicy")]
+    fn unrevealed_policy(&self) -> SingleValueMapper<UnrevealedPolicy>;
+
+    // Move committed by a player during this game round.
+    #[view(getCommit)]
+    #[storage_mapper("commits")]
+    fn commits(&self, game_id: u64, address: &ManagedAddress) -> SingleValueMapper<MoveCommit<Self::Api>>;
+
+    // List of all addresses with a commit not revealed yet during this game round.
+    #[view(getCommittedAddresses)]
+    #[storage_mapper("committedAddresses")]
+    fn committed_addresses(&self, game_id: u64) -> UnorderedSetMapper<ManagedAddress>;
+
+    // Status of the ongoing round of moves.
+    #[view(getRoundStatus)]
+    #[storage_mapper("roundStatus")]
+    fn round_status(&self, game_id: u64) -> SingleValueMapper<RoundStatus>;
+
+    // Amount of payment tokens paid to the caller of resolveRound, for each payment token collected.
+    #[view(getKeeperBounty)]
+    #[storage_mapper("keeperBounty")]
+    fn keeper_bounty(&self, token: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    // Async clock flag (true when the pong contract is used to resolve the rounds).
+    #[view(getAsyncClock)]
+    #[storage_mapper("asyncClock")]
+    fn async_clock(&self) -> SingleValueMapper<bool>;
 
     // Address of the pong contract used to create the clock.
     #[view(getPongAddress)]
@@ -303,5 +1054,17 @@ pub trait StorageModule {
     // List of all addresses with moves during this game round.
     #[view(getAddressesWithMove)]
     #[storage_mapper("addressesWithMove")]
-    fn addresses_with_move(&self) -> UnorderedSetMapper<ManagedAddress>;
+    fn addresses_with_move(&self, game_id: u64) -> UnorderedSetMapper<ManagedAddress>;
+
+    // Moves of the round ongoing in the single maze of the previous contract versions, kept until migrated through migrateMoves.
+    #[storage_mapper("moves")]
+    fn legacy_moves(&self) -> UnorderedSetMapper<PlayerMove<Self::Api>>;
+
+    // Addresses with a move in the round ongoing in the single maze of the previous contract versions.
+    #[storage_mapper("addressesWithMove")]
+    fn legacy_addresses_with_move(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    // Timestamp of the first move of the round ongoing in the single maze of the previous contract versions.
+    #[storage_mapper("timer")]
+    fn legacy_timer(&self) -> SingleValueMapper<u64>;
 }
\ No newline at end of file
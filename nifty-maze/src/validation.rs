@@ -0,0 +1,215 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::errors::{
+    ERR_RAGGED_ROWS,
+    ERR_INVALID_BLOCK,
+    ERR_START_MISSING,
+    ERR_DUPLICATE_START,
+    ERR_FINISH_MISSING,
+    ERR_DUPLICATE_FINISH,
+    ERR_DOOR_WITHOUT_KEY,
+    ERR_TELEPORT_PAIR,
+    ERR_NO_RANDOM_TABLE,
+    ERR_PRIZE_NOT_HELD,
+};
+
+use crate::storage::{
+    Block,
+    Dimensions,
+    PaymentToken,
+    Position,
+    PrizeToWin,
+    PrizeType,
+};
+
+#[multiversx_sc::module]
+pub trait ValidationModule:
+    crate::storage::StorageModule
+{
+    // Function used to check the whole maze matrix of a game session once uploaded.
+    // The matrix dimensions and the start position are set from the valid matrix.
+    fn validate_matrix(
+        &self,
+        game_id: u64,
+    ) {
+        let cols = self.matrix(game_id, 1).len();
+        let mut rows = 0;
+        let mut start_position: Option<Position> = None;
+        let mut finish_found = false;
+        let mut door_ids: ManagedVec<u64> = ManagedVec::new();
+        let mut prizes: ManagedVec<PaymentToken<Self::Api>> = ManagedVec::new();
+
+        while !self.matrix(game_id, rows + 1).is_empty() {
+            rows += 1;
+            // All rows must have the same length.
+            require!(
+                self.matrix(game_id, rows).len() == cols,
+                ERR_RAGGED_ROWS
+            );
+            for (index, block) in self.matrix(game_id, rows).iter().enumerate() {
+                match block {
+                    Block::MatrixDimensions(_, _) | Block::Unknown => sc_panic!(ERR_INVALID_BLOCK),
+                    Block::Start => {
+                        require!(
+                            start_position.is_none(),
+                            ERR_DUPLICATE_START
+                        );
+                        start_position = Some(Position { row: rows, col: index + 1 });
+                    },
+                    Block::Door(door_id) => door_ids.push(door_id),
+                    // Every random block must roll its outcome from a table set by the owner.
+                    Block::Random(table_id) => require!(
+                        !self.random_table(table_id).is_empty(),
+                        ERR_NO_RANDOM_TABLE
+                    ),
+                    Block::Prize(prize_info) => {
+                        if prize_info.prize_type == PrizeType::Finish {
+                            require!(
+                                !finish_found,
+                                ERR_DUPLICATE_FINISH
+                            );
+                            finish_found = true;
+                        }
+                        self.add_prize_amount(&mut prizes, prize_info.ticker, prize_info.nonce, prize_info.quantity);
+                    },
+                    _ => {},
+                }
+            }
+        }
+
+        let start_position = match start_position {
+            Some(position) => position,
+            None => sc_panic!(ERR_START_MISSING),
+        };
+        require!(
+            finish_found,
+            ERR_FINISH_MISSING
+        );
+
+        // Every door must be opened by a key of the maze.
+        for door_id in door_ids.iter() {
+            require!(
+                self.maze_keys_id(game_id).contains(&door_id),
+                ERR_DOOR_WITHOUT_KEY
+            );
+        }
+
+        // Every teleport must lead to exactly one other teleport.
+        for pair_id in self.teleport_ids(game_id).iter() {
+            require!(
+                self.teleports(game_id, pair_id).len() == 2,
+                ERR_TELEPORT_PAIR
+            );
+        }
+
+        // Prizes still to win in the other open games are also paid from the contract balance.
+        for other_game_id in self.games().iter() {
+            if other_game_id == game_id {
+                continue;
+            }
+            for prize in self.prizes_to_win(other_game_id).iter() {
+                if prize.winner.is_zero() {
+                    self.add_prize_amount(&mut prizes, prize.ticker, prize.nonce, prize.amount);
+                }
+            }
+        }
+        for prize in prizes.iter() {
+            require!(
+                self.blockchain().get_sc_balance(&prize.ticker, prize.nonce) >= prize.amount,
+                ERR_PRIZE_NOT_HELD
+            );
+        }
+
+        self.matrix_dimensions(game_id).set(Dimensions { rows, cols });
+        self.start_position(game_id).set(&start_position);
+        self.player_position(game_id).set(start_position);
+    }
+
+    // Function used to register a block set in the matrix in the indexes of the game session.
+    fn register_block(
+        &self,
+        game_id: u64,
+        row: usize,
+        col: usize,
+        block: Block<Self::Api>,
+    ) {
+        match block {
+            // Register prize block in memory.
+            Block::Prize(prize_info) => {
+                let prize_to_win = PrizeToWin {
+                    ticker: prize_info.ticker,
+                    nonce: prize_info.nonce,
+                    amount: prize_info.quantity,
+                    winner: ManagedAddress::zero()  // Assuming winner is zero address initially.
+                };
+                self.prizes_to_win(game_id).insert(prize_to_win);
+            },
+            // Register key id to know which keys can be granted by random blocks.
+            Block::Key(key_id) => {
+                self.maze_keys_id(game_id).insert(key_id);
+            },
+            // Register teleport position with the other teleports sharing its pair id.
+            Block::Teleport(pair_id) => {
+                self.teleport_ids(game_id).insert(pair_id);
+                self.teleports(game_id, pair_id).insert(Position { row, col });
+            },
+            _ => {} // Default case to handle all other block types
+        }
+    }
+
+    // Function used to rebuild the prizes, keys and teleports indexes of the game session from the matrix blocks.
+    // Prizes already won and keys already collected, whose blocks have been emptied, are kept.
+    fn rebuild_indexes(
+        &self,
+        game_id: u64,
+    ) {
+        let mut won_prizes: ManagedVec<PrizeToWin<Self::Api>> = ManagedVec::new();
+        for prize in self.prizes_to_win(game_id).iter() {
+            if !prize.winner.is_zero() {
+                won_prizes.push(prize);
+            }
+        }
+        self.prizes_to_win(game_id).clear();
+        for prize in won_prizes.iter() {
+            self.prizes_to_win(game_id).insert(prize);
+        }
+        self.maze_keys_id(game_id).clear();
+        for key_id in self.collected_keys_id(game_id).iter() {
+            self.maze_keys_id(game_id).insert(key_id);
+        }
+        for pair_id in self.teleport_ids(game_id).iter() {
+            self.teleports(game_id, pair_id).clear();
+        }
+        self.teleport_ids(game_id).clear();
+
+        let mut row = 1;
+        while !self.matrix(game_id, row).is_empty() {
+            for (index, block) in self.matrix(game_id, row).iter().enumerate() {
+                self.register_block(game_id, row, index + 1, block);
+            }
+            row += 1;
+        }
+    }
+
+    // Function used to add a prize amount to the total amount of the same token.
+    fn add_prize_amount(
+        &self,
+        prizes: &mut ManagedVec<PaymentToken<Self::Api>>,
+        ticker: EgldOrEsdtTokenIdentifier,
+        nonce: u64,
+        amount: BigUint,
+    ) {
+        for (index, prize) in prizes.iter().enumerate() {
+            if prize.ticker == ticker && prize.nonce == nonce {
+                let _ = prizes.set(index, &PaymentToken {
+                    ticker,
+                    nonce,
+                    amount: prize.amount + amount,
+                });
+                return;
+            }
+        }
+        prizes.push(PaymentToken { ticker, nonce, amount });
+    }
+}
@@ -0,0 +1,92 @@
+use multiversx_sc_scenario::*;
+
+fn world() -> ScenarioWorld {
+    let mut blockchain = ScenarioWorld::new();
+    blockchain.register_contract("file:output/nifty-maze.wasm", nifty_maze::ContractBuilder);
+    blockchain
+}
+
+#[test]
+fn round_clock_rs() {
+    multiversx_sc_scenario::run_rs("scenarios/round_clock.scen.json", world());
+}
+
+#[test]
+fn commit_reveal_rs() {
+    multiversx_sc_scenario::run_rs("scenarios/commit_reveal.scen.json", world());
+}
+
+#[test]
+fn multi_token_rs() {
+    multiversx_sc_scenario::run_rs("scenarios/multi_token.scen.json", world());
+}
+
+#[test]
+fn revenue_split_rs() {
+    multiversx_sc_scenario::run_rs("scenarios/revenue_split.scen.json", world());
+}
+
+#[test]
+fn leaderboard_rs() {
+    multiversx_sc_scenario::run_rs("scenarios/leaderboard.scen.json", world());
+}
+
+#[test]
+fn pathfinder_rs() {
+    multiversx_sc_scenario::run_rs("scenarios/pathfinder.scen.json", world());
+}
+
+#[test]
+fn fog_of_war_rs() {
+    multiversx_sc_scenario::run_rs("scenarios/fog_of_war.scen.json", world());
+}
+
+#[test]
+fn maze_commitment_rs() {
+    multiversx_sc_scenario::run_rs("scenarios/maze_commitment.scen.json", world());
+}
+
+#[test]
+fn matrix_editing_rs() {
+    multiversx_sc_scenario::run_rs("scenarios/matrix_editing.scen.json", world());
+}
+
+#[test]
+fn maze_upload_rs() {
+    multiversx_sc_scenario::run_rs("scenarios/maze_upload.scen.json", world());
+}
+
+#[test]
+fn special_blocks_rs() {
+    multiversx_sc_scenario::run_rs("scenarios/special_blocks.scen.json", world());
+}
+
+#[test]
+fn stake_weighted_rs() {
+    multiversx_sc_scenario::run_rs("scenarios/stake_weighted.scen.json", world());
+}
+
+#[test]
+fn losing_moves_rs() {
+    multiversx_sc_scenario::run_rs("scenarios/losing_moves.scen.json", world());
+}
+
+#[test]
+fn matrix_validation_rs() {
+    multiversx_sc_scenario::run_rs("scenarios/matrix_validation.scen.json", world());
+}
+
+#[test]
+fn events_rs() {
+    multiversx_sc_scenario::run_rs("scenarios/events.scen.json", world());
+}
+
+#[test]
+fn maze_solution_rs() {
+    multiversx_sc_scenario::run_rs("scenarios/maze_solution.scen.json", world());
+}
+
+#[test]
+fn migrate_moves_rs() {
+    multiversx_sc_scenario::run_rs("scenarios/migrate_moves.scen.json", world());
+}
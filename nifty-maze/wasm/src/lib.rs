@@ -5,9 +5,9 @@
 ////////////////////////////////////////////////////
 
 // Init:                                 1
-// Endpoints:                           46
+// Endpoints:                          151
 // Async Callback (empty):               1
-// Total number of exported functions:  48
+// Total number of exported functions: 153
 
 #![no_std]
 #![feature(lang_items)]
@@ -19,48 +19,153 @@ multiversx_sc_wasm_adapter::endpoints! {
     nifty_maze
     (
         ping
+        resolveRound
+        cancelRound
         addMove
-        mvpClaim
+        commitMove
+        revealMove
+        getMoveHash
+        claimCredit
+        claimRankReward
         getRewardTokens
-        getPaymentToken
-        getPaymentAmount
+        getPaymentTokens
+        getTokenPrice
+        getTokenWeight
+        getLastGameId
+        getGames
         getPlayerPosition
         getStartPosition
         getMatrix
+        getMatrixDimensions
         getFullMatrix
+        getMatrixRows
+        getBlocksAround
+        getFogOfWar
+        getFogRadius
+        getVisitedCells
+        getTeleports
+        getTeleportIds
+        getMazeKeys
+        getRandomTable
+        getBonusPool
         getGameStatus
         getKeys
         getCollectedTokens
+        getFinalCollectedTokens
+        getGameTokens
+        getCollectedValue
         getPlayerPayments
-        getMvpPercent
+        getPlayerValue
+        getRevenueSplit
+        getGameRevenueSplit
+        getTreasuryAddress
+        getRoyaltyAddress
+        getShareDistributed
+        getJackpot
+        getGameJackpot
+        getGameStart
+        getMatrixHash
+        getRoundsPlayed
+        getMazeUpload
+        getMatrixRoot
+        getCellRevealTimeout
         getPlayers
         getPaymentInfo
-        getMvpClaimed
+        getPickedMoves
+        getTotalPickedMoves
+        getCellsAdvanced
+        getMovesSubmitted
+        getTrapsTriggered
+        getKeysCollected
+        getPrizesCollected
+        getPlayerStats
+        getPathfinderPool
+        getPathfinderTokens
+        getPathfinderClaimed
+        getRankCurve
+        getGameRankCurve
+        getRankClaimed
         getPrizesToWin
         getStatus
+        getLeaderboard
         getMvp
         getGeneralData
         getTimer
         getDuration
+        getPlayerMove
         getMoves
-        getCanMakeMove
+        getSelectionMode
+        getLosingMovePolicy
+        getRefundPercent
+        getPlayerCredit
+        getMoveTotals
+        getCommitReveal
+        getRevealDuration
+        getUnrevealedPolicy
+        getCommit
+        getCommittedAddresses
+        getRoundStatus
+        getKeeperBounty
+        getAsyncClock
         getPongAddress
         getAddressesWithMove
         registerPaymentToken
+        removePaymentToken
         loadTokens
         unloadTokens
+        createGame
+        closeGame
         setMatrix
+        beginMazeUpload
+        uploadMazeChunk
+        finalizeMaze
+        setMatrixCommitment
+        setBlock
+        setBlocks
+        insertRow
+        replaceRow
+        migrateMoves
         clearMatrix
         setGameStatus
-        setMvpPercent
+        setRevenueSplit
+        setRankCurve
+        setTreasuryAddress
+        setRoyaltyAddress
+        setRandomTable
+        fundBonusPool
+        fundPathfinderPool
         setInitPosition
         setPongAddress
+        setSelectionMode
+        setLosingMovePolicy
+        setRefundPercent
+        setKeeperBounty
+        setAsyncClock
+        setFogOfWar
+        setFogRadius
+        setCommitReveal
+        setRevealDuration
+        setCellRevealTimeout
+        setUnrevealedPolicy
         setStartPosition
         setPlayerPosition
         setDuration
         setFirstMoveTimestamp
         setStatus
-        setCanMakeMove
+        setRoundStatus
+        getMazeSolution
+        distributeTreasury
+        distributeRoyalty
+        distributeJackpot
+        claimPathfinderReward
+        getGameHistory
+        getGameSummary
+        getRoundRecords
+        getRoundRecord
+        revealCell
+        revealTeleportPair
+        getCellLeaf
+        getCellsToReveal
         pause
         unpause
         isPaused
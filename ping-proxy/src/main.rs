@@ -12,9 +12,11 @@ pub trait PingProxy {
     fn init(&self) {}
 
     // Ping proxy will call the ping method in the caller contract once the caller contract calls the pong method in the ping proxy.
+    // The game id is forwarded to the ping method to keep the clock of the right game session running.
     #[endpoint(pong)]
     fn pong(
         &self,
+        game_id: u64,
     ) {
         require!(
             self.blockchain().get_caller() == self.caller_address().get(),
@@ -22,7 +24,8 @@ pub trait PingProxy {
         );
         let caller_address = self.caller_address().get();
 
-        let arg_buffer = ManagedArgBuffer::new();
+        let mut arg_buffer = ManagedArgBuffer::new();
+        arg_buffer.push_arg(game_id);
         self.send_raw().async_call_raw(
             &caller_address,
             &BigUint::zero(),